@@ -1,20 +1,43 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    attr, to_json_binary, Addr, Attribute, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, Uint128, Uint256, WasmMsg,
 };
-use cw2::set_contract_version;
-use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
+use k256::ecdsa::Signature;
+use semver::Version;
 
-use crate::byte_utils::{extend_address_to_32, ByteUtils};
+use crate::byte_utils::extend_address_to_32;
 use crate::error::ContractError;
-use crate::helpers::keys_equal;
+use crate::events::{GuardianSetUpgradeEvent, VaaVerifiedEvent};
+use crate::helpers::pubkey_matches;
 use crate::msg::{
-    ExecuteMsg, GetAddressHexResponse, GuardianSetInfoResponse, InstantiateMsg, QueryMsg,
+    AllowedSubmittersResponse, ConsistencyLevelResponse, EmitterRegistrationResponse, ExecuteMsg,
+    FreshnessPolicyResponse, GetAddressHexResponse, GovernanceEmittersResponse,
+    GovernanceHistoryResponse, GovernorUsageResponse, GuardianSetInfoResponse, InstantiateMsg,
+    LastSequenceResponse, MigrateMsg, MissingSequencesResponse, OwnershipAction,
+    PauseStatusResponse, PendingGovernanceActionsResponse, PendingVAAsResponse, QueryMsg,
+    QuorumResponse, ReceiverExecuteMsg, SudoMsg, TokenPriceResponse, VAAReceiptResponse,
+    VAAStatusResponse, VerifyQueryResponseResponse, VerifySignaturesResponse, VerifyVAAResponse,
 };
+use crate::query_response::{query_response_digest, QueryResponse};
 use crate::state::{
-    ConfigInfo, GovernancePacket, GuardianSetUpgrade, ParsedVAA, CONFIG, CONSUMED_VAA_ARCHIVE,
-    GUARDIAN_SET,
+    CancelPendingGovernance, ConfigInfo, ConsumedVAA, EmitterRegistration, ExternalAddress,
+    Finality, FreshnessPolicy, GovernanceEmitter, GovernanceLogEntry, GovernanceOrigin,
+    GovernancePacket, GovernorLimit, GovernorUsage, GuardianSetInfo, GuardianSetUpgrade,
+    GuardianSignature, GuardianSigner, Ownership, ParsedVAA, PendingGovernanceAction, PendingVAA,
+    QuorumConfig, QuorumRule, RegisterEmitter, ReleasePendingVAA, RevokeAdmin, SetConsistencyLevel,
+    SetFreshnessPolicy, SetGovernanceDelay, SetGovernorLimit, SetGuardianSetExpiration,
+    SetGuardianSetExpiry, SetPaused, SetTokenPrice, TokenBridgeTransfer, UpdateGovernanceEmitter,
+    VAAAction, VAAReceipt, VaaDigest, VaaRef, VaaSignature, ALLOWED_SUBMITTERS,
+    CHAIN_FRESHNESS_POLICY, CONFIG, CONSUMED_VAA_ARCHIVE, CONSUMED_VAA_BY_MESSAGE_ID,
+    EMITTER_REGISTRATIONS, FRESHNESS_POLICY, GOVERNANCE_DELAY, GOVERNANCE_EMITTERS,
+    GOVERNANCE_HISTORY, GOVERNANCE_HISTORY_LEN, GOVERNOR_LIMITS, GOVERNOR_USAGE, GOVERNOR_WINDOW,
+    GUARDIAN_SET, LAST_SEQUENCE, LEGACY_CONFIG, LEGACY_CONSUMED_VAA_ARCHIVE, MIN_CONSISTENCY_LEVEL,
+    NEXT_PENDING_GOVERNANCE_ID, OWNERSHIP, PAUSED, PENDING_GOVERNANCE, PENDING_STRICT_SEQUENCES,
+    PENDING_VAAS, QUORUM_CONFIG, SEEN_SEQUENCES, TOKEN_PRICES,
 };
 
 type HumanAddr = String;
@@ -23,6 +46,20 @@ type HumanAddr = String;
 const CONTRACT_NAME: &str = "crates.io:wormhole";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Maximum number of sequences scanned by a single missing sequences query
+const MAX_SEQUENCE_RANGE: u64 = 4096;
+
+// Only governance module of the core contract
+const CORE_MODULE: &str = "Core";
+
+// Pagination of list queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+// Archive entries rewritten by a single migration
+const DEFAULT_MIGRATION_LIMIT: u32 = 500;
+const MAX_MIGRATION_LIMIT: u32 = 1000;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -32,24 +69,174 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     // Save general wormhole info
     let config = ConfigInfo {
-        gov_chain: msg.gov_chain,
-        gov_address: msg.gov_address.as_slice().to_vec(),
         guardian_set_index: 0,
         guardian_set_expirity: msg.guardian_set_expirity,
         chain_id: msg.chain_id,
     };
 
+    if msg.test_mode && !cfg!(feature = "testing") {
+        return Err(ContractError::TestModeUnavailable {});
+    }
+    let quorum_config = QuorumConfig {
+        rule: msg.quorum_rule.unwrap_or(QuorumRule::Wormhole {}),
+        test_mode: msg.test_mode,
+    };
+    quorum_config.rule.validate()?;
+    msg.initial_guardian_set.validate(&quorum_config)?;
+
     CONFIG.save(deps.storage, &config)?;
+    GOVERNANCE_EMITTERS.save(
+        deps.storage,
+        (msg.gov_chain, msg.gov_address.as_slice()),
+        &Empty {},
+    )?;
+    QUORUM_CONFIG.save(deps.storage, &quorum_config)?;
     GUARDIAN_SET.save(
         deps.storage,
         config.guardian_set_index,
         &msg.initial_guardian_set,
     )?;
+    for policy in msg.consistency_levels {
+        MIN_CONSISTENCY_LEVEL.save(deps.storage, policy.emitter_chain, &policy.min_finality)?;
+    }
+    if let Some(policy) = msg.freshness_policy {
+        FRESHNESS_POLICY.save(deps.storage, &policy)?;
+    }
+    for chain_policy in msg.chain_freshness_policies {
+        CHAIN_FRESHNESS_POLICY.save(
+            deps.storage,
+            chain_policy.emitter_chain,
+            &chain_policy.policy,
+        )?;
+    }
+    if msg.governance_delay > 0 {
+        GOVERNANCE_DELAY.save(deps.storage, &msg.governance_delay)?;
+    }
+    if let Some(admin) = msg.admin {
+        let ownership = Ownership {
+            owner: Some(deps.api.addr_validate(&admin)?),
+            ..Ownership::default()
+        };
+        OWNERSHIP.save(deps.storage, &ownership)?;
+    }
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::default())
 }
 
+/// Recovery by the chain governance module when guardian governance is unreachable
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(mut deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::SetGuardianSet {
+            index,
+            guardian_set,
+        } => {
+            // The current guardian set never expires
+            let guardian_set = GuardianSetInfo {
+                expiration_time: 0,
+                ..guardian_set
+            };
+            let (res, log) = update_guardian_set(deps.branch(), env.clone(), index, guardian_set)?;
+            // Recorded as the guardian set upgrade action 2
+            append_governance_history(deps.storage, &env, GovernanceOrigin::Sudo, None, 2, log)?;
+            Ok(res.add_attribute("origin", "sudo"))
+        }
+        SudoMsg::ExpireGuardianSet { index } => {
+            let expiration_time =
+                update_guardian_set_expiration(deps.branch(), &env, index, 0, true)?;
+            let (res, log) = GovernanceLog::new(
+                "set_guardian_set_expiration",
+                vec![
+                    attr("guardian_set_index", index.to_string()),
+                    attr("expiration_time", expiration_time.to_string()),
+                ],
+            )
+            .into_response()?;
+            // Recorded as the guardian set expiration action 132
+            append_governance_history(deps.storage, &env, GovernanceOrigin::Sudo, None, 132, log)?;
+            Ok(res.add_attribute("origin", "sudo"))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::WrongContract {});
+    }
+    let parse = |version: &str| {
+        Version::parse(version).map_err(|err| StdError::generic_err(err.to_string()))
+    };
+    if parse(&stored.version)? > parse(CONTRACT_VERSION)? {
+        return Err(ContractError::CannotDowngrade {});
+    }
+
+    // Move the governance emitter of a 0.1.0 config to the governance emitter set
+    if let Ok(legacy) = LEGACY_CONFIG.load(deps.storage) {
+        let gov_address = ExternalAddress::try_from(legacy.gov_address.as_slice())?;
+        GOVERNANCE_EMITTERS.save(
+            deps.storage,
+            (legacy.gov_chain, gov_address.as_slice()),
+            &Empty {},
+        )?;
+        let config = ConfigInfo {
+            guardian_set_index: legacy.guardian_set_index,
+            guardian_set_expirity: legacy.guardian_set_expirity,
+            chain_id: legacy.chain_id,
+        };
+        CONFIG.save(deps.storage, &config)?;
+    }
+
+    // Replace the bare `true` entries of the consumed VAA archive with legacy receipts, one
+    // batch per migration so that large archives fit in the gas limit
+    let limit = msg
+        .limit
+        .unwrap_or(DEFAULT_MIGRATION_LIMIT)
+        .min(MAX_MIGRATION_LIMIT) as usize;
+    let start = msg
+        .start_after
+        .as_ref()
+        .map(|digest| Bound::exclusive(digest.as_slice()));
+    let digests = LEGACY_CONSUMED_VAA_ARCHIVE
+        .keys_raw(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<Vec<_>>();
+
+    let mut legacy_digests = vec![];
+    for digest in &digests {
+        match LEGACY_CONSUMED_VAA_ARCHIVE.load(deps.storage, digest) {
+            Ok(_) => legacy_digests.push(digest),
+            // Entries that already decode as receipts were migrated before
+            Err(_) if CONSUMED_VAA_ARCHIVE.load(deps.storage, digest).is_ok() => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    let receipt = VAAReceipt {
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+        submitter: None,
+        emitter_chain: 0,
+        emitter_address: ExternalAddress::default(),
+        sequence: 0,
+        action: VAAAction::Legacy {},
+    };
+    for digest in &legacy_digests {
+        CONSUMED_VAA_ARCHIVE.save(deps.storage, digest, &receipt)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let last_digest = digests.last().map(hex::encode).unwrap_or_default();
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("migrated_receipts", legacy_digests.len().to_string())
+        .add_attribute("last_digest", last_digest)
+        .add_attribute("complete", (digests.len() < limit).to_string()))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -59,16 +246,145 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::SubmitVAA { vaa } => handle_submit_vaa(deps, env, info, vaa.as_slice()),
+        ExecuteMsg::UpdateOwnership(action) => handle_update_ownership(deps, env, info, action),
+        ExecuteMsg::Pause {} => handle_pause(deps, info),
+        ExecuteMsg::ShortenGuardianSetExpiration {
+            guardian_set_index,
+            expiration_time,
+        } => handle_shorten_guardian_set_expiration(
+            deps,
+            env,
+            info,
+            guardian_set_index,
+            expiration_time,
+        ),
+        ExecuteMsg::ExecutePending { id } => handle_execute_pending(deps, env, id),
+        ExecuteMsg::ReleasePendingVAA { digest } => handle_release_pending_vaa(deps, env, digest),
+        ExecuteMsg::RestrictSubmitters { submitters } => {
+            handle_restrict_submitters(deps, info, submitters)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GuardianSetInfo {} => to_json_binary(&query_guardian_set_info(deps)?),
-        QueryMsg::VerifyVAA { vaa, block_time } => {
-            to_json_binary(&parse_and_verify_vaa(deps, vaa.as_slice(), block_time)?)
+        QueryMsg::VerifyVAA {
+            vaa,
+            block_time,
+            strict,
+        } => to_json_binary(&query_verify_vaa(
+            deps,
+            vaa.as_slice(),
+            block_time,
+            strict.unwrap_or(false),
+        )?),
+        QueryMsg::VerifyVAAWithThreshold {
+            vaa,
+            block_time,
+            min_signatures,
+        } => to_json_binary(&query_verify_vaa_with_threshold(
+            deps,
+            vaa.as_slice(),
+            block_time,
+            min_signatures,
+        )?),
+        QueryMsg::VerifySignatures {
+            digest,
+            signatures,
+            guardian_set_index,
+            block_time,
+        } => to_json_binary(&query_verify_signatures(
+            deps,
+            &digest,
+            &signatures,
+            guardian_set_index,
+            block_time,
+        )?),
+        QueryMsg::VerifyQueryResponse {
+            response,
+            signatures,
+        } => to_json_binary(&query_verify_query_response(
+            deps,
+            env.block.time.seconds(),
+            &response,
+            &signatures,
+        )?),
+        QueryMsg::Quorum {} => to_json_binary(&query_quorum(deps)?),
+        QueryMsg::ConsistencyLevel { emitter_chain } => {
+            to_json_binary(&query_consistency_level(deps, emitter_chain)?)
+        }
+        QueryMsg::FreshnessPolicy { emitter_chain } => {
+            to_json_binary(&query_freshness_policy(deps, emitter_chain)?)
+        }
+        QueryMsg::EmitterRegistration {
+            emitter_chain,
+            emitter_address,
+        } => to_json_binary(&query_emitter_registration(
+            deps,
+            emitter_chain,
+            emitter_address.as_slice(),
+        )?),
+        QueryMsg::LastSequence {
+            emitter_chain,
+            emitter_address,
+        } => to_json_binary(&query_last_sequence(
+            deps,
+            emitter_chain,
+            emitter_address.as_slice(),
+        )?),
+        QueryMsg::MissingSequences {
+            emitter_chain,
+            emitter_address,
+            start,
+            end,
+        } => to_json_binary(&query_missing_sequences(
+            deps,
+            emitter_chain,
+            emitter_address.as_slice(),
+            start,
+            end,
+        )?),
+        QueryMsg::VAAStatus {
+            emitter_chain,
+            emitter_address,
+            sequence,
+        } => to_json_binary(&query_vaa_status(
+            deps,
+            emitter_chain,
+            emitter_address.as_slice(),
+            sequence,
+        )?),
+        QueryMsg::VAAReceipt { digest } => {
+            to_json_binary(&query_vaa_receipt(deps, digest.as_slice())?)
+        }
+        QueryMsg::GovernorUsage { emitter_chain } => to_json_binary(&query_governor_usage(
+            deps,
+            env.block.time.seconds(),
+            emitter_chain,
+        )?),
+        QueryMsg::TokenPrice {
+            token_chain,
+            token_address,
+        } => to_json_binary(&query_token_price(
+            deps,
+            token_chain,
+            token_address.as_slice(),
+        )?),
+        QueryMsg::PendingVAAs { start_after, limit } => {
+            to_json_binary(&query_pending_vaas(deps, start_after, limit)?)
         }
+        QueryMsg::PendingGovernanceActions { start_after, limit } => {
+            to_json_binary(&query_pending_governance_actions(deps, start_after, limit)?)
+        }
+        QueryMsg::GovernanceHistory { start_after, limit } => {
+            to_json_binary(&query_governance_history(deps, start_after, limit)?)
+        }
+        QueryMsg::GovernanceEmitters {} => to_json_binary(&query_governance_emitters(deps)?),
+        QueryMsg::Ownership {} => to_json_binary(&query_ownership(deps)?),
+        QueryMsg::AllowedSubmitters {} => to_json_binary(&query_allowed_submitters(deps)?),
+        QueryMsg::PauseStatus {} => to_json_binary(&query_pause_status(deps)?),
         QueryMsg::QueryAddressHex { address } => {
             to_json_binary(&query_address_hex(deps, &address)?)
         }
@@ -79,28 +395,369 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 fn handle_submit_vaa(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     data: &[u8],
+) -> Result<Response, ContractError> {
+    let (vaa, _) = parse_and_verify_vaa(deps.as_ref(), data, env.block.time.seconds())?;
+    let event = VaaVerifiedEvent {
+        digest: vaa.hash,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+    };
+
+    let res = consume_vaa(deps, env, info, vaa)?;
+    Ok(res.add_event(event))
+}
+
+/// Applies the policies of the contract to a verified VAA, then executes it as governance or
+/// delivers it to the receiver of its emitter
+fn consume_vaa(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vaa: ParsedVAA,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    check_consistency_level(deps.as_ref(), &vaa)?;
 
-    let vaa = parse_and_verify_vaa(deps.as_ref(), data, env.block.time.seconds())?;
-    CONSUMED_VAA_ARCHIVE.save(deps.storage, vaa.hash.as_slice(), &true)?;
+    if CONSUMED_VAA_ARCHIVE.has(deps.storage, vaa.hash.as_slice()) {
+        return Err(ContractError::VaaAlreadyExecuted {});
+    }
+    CONSUMED_VAA_BY_MESSAGE_ID.save(
+        deps.storage,
+        (
+            vaa.emitter_chain,
+            vaa.emitter_address.as_slice(),
+            vaa.sequence,
+        ),
+        &ConsumedVAA {
+            digest: vaa.hash,
+            block_height: env.block.height,
+            block_time: env.block.time.seconds(),
+        },
+    )?;
 
-    if config.gov_chain == vaa.emitter_chain && config.gov_address == vaa.emitter_address {
+    let emitter = (vaa.emitter_chain, vaa.emitter_address.as_slice());
+    if GOVERNANCE_EMITTERS.has(deps.storage, emitter) {
         if config.guardian_set_index != vaa.guardian_set_index {
             return Err(ContractError::InvalidGovernanceVaa {});
         }
 
-        return handle_governance_payload(deps, env, &vaa.payload);
+        record_sequence(deps.storage, &vaa, false)?;
+        let action = VAAAction::Governance {
+            action: GovernancePacket::deserialize(&vaa.payload)?.action,
+        };
+        save_receipt(deps.storage, &env, info, &vaa, action)?;
+        let emitter = GovernanceEmitter {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        };
+        return handle_governance_payload(deps, env, emitter, vaa.hash, &vaa.payload);
+    }
+
+    // Governance VAAs keep being processed while paused so the contract can be unpaused or
+    // upgraded. This contract has no PostMessage, SubmitVAA is the only entry point to gate.
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+
+    if let Some(submitters) = ALLOWED_SUBMITTERS.may_load(deps.storage)? {
+        if !submitters.contains(&info.sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+
+    // Governance VAAs are exempt so that historical guardian set upgrades can still be replayed
+    check_freshness(deps.as_ref(), &vaa, env.block.time.seconds())?;
+
+    let registration = EMITTER_REGISTRATIONS
+        .may_load(
+            deps.storage,
+            (vaa.emitter_chain, vaa.emitter_address.as_slice()),
+        )?
+        .ok_or(ContractError::InvalidVAAAction {})?;
+    record_sequence(deps.storage, &vaa, registration.strict_ordering)?;
+
+    if let Some(pending) = governor_check(deps.storage, &env, &vaa, &registration)? {
+        let action = VAAAction::Queued {
+            receiver: registration.receiver,
+        };
+        save_receipt(deps.storage, &env, info, &vaa, action)?;
+        PENDING_VAAS.save(deps.storage, vaa.hash.as_slice(), &pending)?;
+        if registration.strict_ordering {
+            let key = (
+                vaa.emitter_chain,
+                vaa.emitter_address.as_slice(),
+                vaa.sequence,
+            );
+            PENDING_STRICT_SEQUENCES.save(deps.storage, key, &Empty {})?;
+        }
+
+        return Ok(Response::new()
+            .add_attribute("action", "queue_vaa")
+            .add_attribute("emitter_chain", vaa.emitter_chain.to_string())
+            .add_attribute("sequence", vaa.sequence.to_string())
+            .add_attribute("release_time", pending.release_time.to_string()));
+    }
+
+    let action = VAAAction::Dispatched {
+        receiver: registration.receiver.clone(),
+    };
+    save_receipt(deps.storage, &env, info, &vaa, action)?;
+
+    dispatch_vaa(vaa, registration.receiver)
+}
+
+/// Delivers a verified VAA to the receiver registered for its emitter
+fn dispatch_vaa(vaa: ParsedVAA, receiver: Addr) -> Result<Response, ContractError> {
+    Ok(Response::new()
+        .add_attribute("action", "receive_vaa")
+        .add_attribute("emitter_chain", vaa.emitter_chain.to_string())
+        .add_attribute("sequence", vaa.sequence.to_string())
+        .add_attribute("receiver", receiver.as_str())
+        .add_message(WasmMsg::Execute {
+            contract_addr: receiver.into_string(),
+            msg: to_json_binary(&ReceiverExecuteMsg::ReceiveVAA { vaa })?,
+            funds: vec![],
+        }))
+}
+
+/// Removes the usage entries of the emitter chain that fell out of the governor window
+fn prune_governor_usage(
+    storage: &mut dyn Storage,
+    emitter_chain: u16,
+    block_time: u64,
+) -> StdResult<()> {
+    let window_start = block_time.saturating_sub(GOVERNOR_WINDOW);
+    let expired = GOVERNOR_USAGE
+        .prefix(emitter_chain)
+        .keys(
+            storage,
+            None,
+            Some(Bound::inclusive(window_start)),
+            Order::Ascending,
+        )
+        .collect::<StdResult<Vec<_>>>()?;
+    for time in expired {
+        GOVERNOR_USAGE.remove(storage, (emitter_chain, time));
+    }
+
+    Ok(())
+}
+
+/// Sums the usage of the emitter chain over the governor window ending at `block_time`
+fn governor_usage(
+    storage: &dyn Storage,
+    emitter_chain: u16,
+    block_time: u64,
+) -> StdResult<GovernorUsage> {
+    let window_start = block_time.saturating_sub(GOVERNOR_WINDOW);
+    GOVERNOR_USAGE
+        .prefix(emitter_chain)
+        .range(
+            storage,
+            Some(Bound::exclusive(window_start)),
+            None,
+            Order::Ascending,
+        )
+        .try_fold(GovernorUsage::default(), |total, item| {
+            let (_, usage) = item?;
+            Ok(GovernorUsage {
+                vaas: total.vaas + usage.vaas,
+                notional: total.notional.saturating_add(usage.notional),
+            })
+        })
+}
+
+/// Records the VAA against the governor limit of its emitter chain. Returns the pending entry
+/// instead if the VAA would exceed the limit, or if it has to wait behind a queued VAA of its
+/// strict ordering emitter.
+fn governor_check(
+    storage: &mut dyn Storage,
+    env: &Env,
+    vaa: &ParsedVAA,
+    registration: &EmitterRegistration,
+) -> StdResult<Option<PendingVAA>> {
+    let emitter = (vaa.emitter_chain, vaa.emitter_address.as_slice());
+    let held_back = registration.strict_ordering
+        && PENDING_STRICT_SEQUENCES
+            .prefix(emitter)
+            .keys_raw(storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+    let limit = match GOVERNOR_LIMITS.may_load(storage, vaa.emitter_chain)? {
+        Some(limit) => limit,
+        // Successors of a queued VAA still queue once the limit is lifted, without a delay
+        None if held_back => GovernorLimit {
+            max_vaas: None,
+            max_notional: None,
+            release_delay: 0,
+        },
+        None => return Ok(None),
+    };
+
+    // Other emitters may use any payload layout, only token bridge transfers carry a value
+    let transfer = match registration.token_bridge {
+        true => TokenBridgeTransfer::deserialize(&vaa.payload),
+        false => None,
+    };
+    let notional = match transfer {
+        Some(transfer) => transfer_notional(storage, &transfer)?,
+        None => Uint128::zero(),
+    };
+    let block_time = env.block.time.seconds();
+    prune_governor_usage(storage, vaa.emitter_chain, block_time)?;
+    let usage = governor_usage(storage, vaa.emitter_chain, block_time)?;
+
+    let over_vaas = limit.max_vaas.is_some_and(|max| usage.vaas >= max);
+    let over_notional = limit
+        .max_notional
+        .is_some_and(|max| usage.notional.saturating_add(notional) > max);
+    if held_back || over_vaas || over_notional {
+        return Ok(Some(PendingVAA {
+            vaa: vaa.clone(),
+            receiver: registration.receiver.clone(),
+            notional,
+            queued_at: block_time,
+            release_time: block_time.saturating_add(limit.release_delay),
+        }));
+    }
+
+    GOVERNOR_USAGE.update(storage, (vaa.emitter_chain, block_time), |usage| {
+        let usage = usage.unwrap_or_default();
+        StdResult::Ok(GovernorUsage {
+            vaas: usage.vaas + 1,
+            notional: usage.notional.saturating_add(notional),
+        })
+    })?;
+
+    Ok(None)
+}
+
+/// USD value of a token bridge transfer at the price of its token, truncated to whole dollars.
+/// Tokens without a price are not valued.
+fn transfer_notional(storage: &dyn Storage, transfer: &TokenBridgeTransfer) -> StdResult<Uint128> {
+    let key = (transfer.token_chain, transfer.token_address.as_slice());
+    let Some(price) = TOKEN_PRICES.may_load(storage, key)? else {
+        return Ok(Uint128::zero());
+    };
+
+    // Amounts and prices both have 8 decimals
+    let notional = transfer.amount.full_mul(price) / Uint256::from(10u128.pow(16));
+    Ok(Uint128::try_from(notional).unwrap_or(Uint128::MAX))
+}
+
+/// Removes a queued VAA and delivers it, released VAAs do not count against the limits. VAAs
+/// of strict ordering emitters are only released after all their queued predecessors.
+fn release_pending_vaa(
+    storage: &mut dyn Storage,
+    digest: &VaaDigest,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_VAAS
+        .may_load(storage, digest.as_slice())?
+        .ok_or(ContractError::PendingVAANotFound {})?;
+
+    let emitter = (
+        pending.vaa.emitter_chain,
+        pending.vaa.emitter_address.as_slice(),
+    );
+    let key = (emitter.0, emitter.1, pending.vaa.sequence);
+    if PENDING_STRICT_SEQUENCES.has(storage, key) {
+        let first = PENDING_STRICT_SEQUENCES
+            .prefix(emitter)
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .transpose()?;
+        if first != Some(pending.vaa.sequence) {
+            return Err(ContractError::SequenceOutOfOrder {});
+        }
+        PENDING_STRICT_SEQUENCES.remove(storage, key);
+    }
+    PENDING_VAAS.remove(storage, digest.as_slice());
+
+    CONSUMED_VAA_ARCHIVE.update(storage, digest.as_slice(), |receipt| {
+        let mut receipt = receipt.ok_or(ContractError::PendingVAANotFound {})?;
+        receipt.action = VAAAction::Dispatched {
+            receiver: pending.receiver.clone(),
+        };
+        Ok::<_, ContractError>(receipt)
+    })?;
+
+    dispatch_vaa(pending.vaa, pending.receiver)
+}
+
+fn handle_release_pending_vaa(
+    deps: DepsMut,
+    env: Env,
+    digest: VaaDigest,
+) -> Result<Response, ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+
+    let pending = PENDING_VAAS
+        .may_load(deps.storage, digest.as_slice())?
+        .ok_or(ContractError::PendingVAANotFound {})?;
+    if env.block.time.seconds() < pending.release_time {
+        return Err(ContractError::ReleaseTimeNotReached {});
+    }
+
+    release_pending_vaa(deps.storage, &digest)
+}
+
+fn save_receipt(
+    storage: &mut dyn Storage,
+    env: &Env,
+    info: MessageInfo,
+    vaa: &ParsedVAA,
+    action: VAAAction,
+) -> StdResult<()> {
+    let receipt = VAAReceipt {
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+        submitter: Some(info.sender),
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        action,
+    };
+    CONSUMED_VAA_ARCHIVE.save(storage, vaa.hash.as_slice(), &receipt)
+}
+
+/// Tracks the consumed sequence of the VAA emitter. With strict ordering the VAA must be the
+/// direct successor of the last consumed sequence.
+fn record_sequence(
+    storage: &mut dyn Storage,
+    vaa: &ParsedVAA,
+    strict_ordering: bool,
+) -> Result<(), ContractError> {
+    let emitter = (vaa.emitter_chain, vaa.emitter_address.as_slice());
+    let last_sequence = LAST_SEQUENCE.may_load(storage, emitter)?;
+
+    if strict_ordering
+        && last_sequence.is_some_and(|last| last.checked_add(1) != Some(vaa.sequence))
+    {
+        return Err(ContractError::SequenceOutOfOrder {});
+    }
+
+    // None orders before any sequence
+    if last_sequence < Some(vaa.sequence) {
+        LAST_SEQUENCE.save(storage, emitter, &vaa.sequence)?;
     }
 
-    Err(ContractError::InvalidVAAAction {})
+    let key = (emitter.0, emitter.1, vaa.sequence / 64);
+    let bitmap = SEEN_SEQUENCES.may_load(storage, key)?.unwrap_or_default();
+    SEEN_SEQUENCES.save(storage, key, &(bitmap | 1 << (vaa.sequence % 64)))?;
+
+    Ok(())
 }
 
 fn handle_governance_payload(
     deps: DepsMut,
     env: Env,
+    emitter: GovernanceEmitter,
+    digest: VaaDigest,
     data: &[u8],
 ) -> Result<Response, ContractError> {
     let gov_packet = GovernancePacket::deserialize(data)?;
@@ -109,7 +766,7 @@ fn handle_governance_payload(
     let module = String::from_utf8(gov_packet.module).unwrap();
     let module: String = module.chars().filter(|c| c != &'\0').collect();
 
-    if module != "Core" {
+    if module != CORE_MODULE {
         return Err(ContractError::InvalidModule {});
     }
 
@@ -117,54 +774,270 @@ fn handle_governance_payload(
         return Err(ContractError::InvalidGovernanceVaa {});
     }
 
-    match gov_packet.action {
-        // 1u8 => vaa_update_contract(deps, env, &gov_packet.payload),
-        2u8 => vaa_update_guardian_set(deps, env, &gov_packet.payload),
+    // Non-critical actions wait out the governance delay, guardian set and emergency actions
+    // always apply immediately
+    let delay = GOVERNANCE_DELAY.may_load(deps.storage)?.unwrap_or_default();
+    if delay > 0 && timelocked_payload_check(gov_packet.action).is_some() {
+        return queue_governance_action(
+            deps,
+            env,
+            delay,
+            emitter,
+            digest,
+            gov_packet.action,
+            gov_packet.payload,
+        );
+    }
+
+    execute_governance_action(deps, env, digest, gov_packet.action, &gov_packet.payload)
+}
+
+/// Applies a governance action of the Core module and appends it to the governance history
+fn execute_governance_action(
+    mut deps: DepsMut,
+    env: Env,
+    digest: VaaDigest,
+    action: u8,
+    payload: &[u8],
+) -> Result<Response, ContractError> {
+    let (res, log) = apply_governance_action(deps.branch(), env.clone(), action, payload)?;
+    let origin = GovernanceOrigin::Vaa;
+    append_governance_history(deps.storage, &env, origin, Some(digest), action, log)?;
+
+    Ok(res)
+}
+
+/// Name and parameters of an executed governance action, as recorded in the governance history
+struct GovernanceLog {
+    name: &'static str,
+    params: Vec<Attribute>,
+}
+
+/// Response of a governance handler together with its history record
+type GovernanceResult = Result<(Response, GovernanceLog), ContractError>;
+
+impl GovernanceLog {
+    fn new(name: &'static str, params: Vec<Attribute>) -> Self {
+        GovernanceLog { name, params }
+    }
+
+    /// Reports the log as the response attributes, for handlers with nothing else to report
+    fn into_response(self) -> GovernanceResult {
+        let res = Response::new()
+            .add_attribute("action", self.name)
+            .add_attributes(self.params.clone());
+        Ok((res, self))
+    }
+}
+
+fn append_governance_history(
+    storage: &mut dyn Storage,
+    env: &Env,
+    origin: GovernanceOrigin,
+    digest: Option<VaaDigest>,
+    action: u8,
+    log: GovernanceLog,
+) -> StdResult<()> {
+    let id = GOVERNANCE_HISTORY_LEN
+        .may_load(storage)?
+        .unwrap_or_default();
+    let entry = GovernanceLogEntry {
+        id,
+        action,
+        name: log.name.to_string(),
+        module: CORE_MODULE.to_string(),
+        origin,
+        digest,
+        params: log.params,
+        block_height: env.block.height,
+        block_time: env.block.time.seconds(),
+    };
+    GOVERNANCE_HISTORY.save(storage, id, &entry)?;
+    GOVERNANCE_HISTORY_LEN.save(storage, &(id + 1))
+}
+
+type PayloadCheck = fn(&[u8]) -> Result<(), ContractError>;
+
+/// Actions subject to the governance delay, each with the check of its payload run when the
+/// action is queued
+const TIMELOCKED_ACTIONS: &[(u8, PayloadCheck)] = &[
+    (128u8, |p| SetConsistencyLevel::deserialize(p).map(drop)),
+    (129u8, |p| SetFreshnessPolicy::deserialize(p).map(drop)),
+    (130u8, |p| RegisterEmitter::deserialize(p).map(drop)),
+    (135u8, |p| SetGovernorLimit::deserialize(p).map(drop)),
+    (137u8, |p| SetGovernanceDelay::deserialize(p).map(drop)),
+    (138u8, |p| UpdateGovernanceEmitter::deserialize(p).map(drop)),
+    (139u8, |p| UpdateGovernanceEmitter::deserialize(p).map(drop)),
+    (141u8, |p| SetTokenPrice::deserialize(p).map(drop)),
+];
+
+/// Payload check of a timelocked action, None for actions that always apply immediately
+fn timelocked_payload_check(action: u8) -> Option<PayloadCheck> {
+    TIMELOCKED_ACTIONS
+        .iter()
+        .find(|(timelocked, _)| *timelocked == action)
+        .map(|(_, check)| *check)
+}
+
+fn apply_governance_action(
+    deps: DepsMut,
+    env: Env,
+    action: u8,
+    payload: &[u8],
+) -> GovernanceResult {
+    match action {
+        // 1u8 => vaa_update_contract(deps, env, payload),
+        2u8 => vaa_update_guardian_set(deps, env, payload),
+        // Actions from 128 onwards are extensions of this contract and are not part of the
+        // upstream Core module
+        128u8 => vaa_set_consistency_level(deps, payload),
+        129u8 => vaa_set_freshness_policy(deps, payload),
+        130u8 => vaa_register_emitter(deps, payload),
+        131u8 => vaa_set_guardian_set_expiry(deps, payload),
+        132u8 => vaa_set_guardian_set_expiration(deps, env, payload),
+        133u8 => vaa_set_paused(deps, payload),
+        134u8 => vaa_revoke_admin(deps, payload),
+        135u8 => vaa_set_governor_limit(deps, payload),
+        136u8 => vaa_release_pending_vaa(deps, payload),
+        137u8 => vaa_set_governance_delay(deps, payload),
+        138u8 => vaa_add_governance_emitter(deps, payload),
+        139u8 => vaa_remove_governance_emitter(deps, payload),
+        140u8 => vaa_cancel_pending_governance(deps, payload),
+        141u8 => vaa_set_token_price(deps, payload),
         // #[cfg(feature = "full")]
-        // 3u8 => handle_set_fee(deps, env, &gov_packet.payload),
+        // 3u8 => handle_set_fee(deps, env, payload),
         // #[cfg(feature = "full")]
-        // 4u8 => handle_transfer_fee(deps, env, &gov_packet.payload),
+        // 4u8 => handle_transfer_fee(deps, env, payload),
         _ => Err(ContractError::InvalidVAAAction {}),
     }
 }
 
+/// Stores a timelocked action after checking its payload decodes, so that malformed actions
+/// are rejected on submission rather than on execution
+fn queue_governance_action(
+    deps: DepsMut,
+    env: Env,
+    delay: u64,
+    emitter: GovernanceEmitter,
+    digest: VaaDigest,
+    action: u8,
+    payload: Vec<u8>,
+) -> Result<Response, ContractError> {
+    let check = timelocked_payload_check(action).ok_or(ContractError::InvalidVAAAction {})?;
+    check(&payload)?;
+
+    let id = NEXT_PENDING_GOVERNANCE_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    NEXT_PENDING_GOVERNANCE_ID.save(deps.storage, &(id + 1))?;
+
+    let pending = PendingGovernanceAction {
+        id,
+        emitter,
+        digest,
+        action,
+        payload: payload.into(),
+        queued_at: env.block.time.seconds(),
+        executable_at: env.block.time.seconds().saturating_add(delay),
+    };
+    PENDING_GOVERNANCE.save(deps.storage, id, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "queue_governance_action")
+        .add_attribute("id", id.to_string())
+        .add_attribute("governance_action", action.to_string())
+        .add_attribute("executable_at", pending.executable_at.to_string()))
+}
+
+fn handle_execute_pending(mut deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let pending = PENDING_GOVERNANCE
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::PendingActionNotFound {})?;
+    if env.block.time.seconds() < pending.executable_at {
+        return Err(ContractError::TimelockNotExpired {});
+    }
+
+    // Actions failing to execute stay queued until governance cancels them with action 140
+    let res = execute_governance_action(
+        deps.branch(),
+        env,
+        pending.digest,
+        pending.action,
+        &pending.payload,
+    )?;
+    PENDING_GOVERNANCE.remove(deps.storage, id);
+    Ok(res.add_attribute("pending_id", id.to_string()))
+}
+
 /// Parses raw VAA data into a struct and verifies whether it contains sufficient signatures of an
-/// active guardian set i.e. is valid according to Wormhole consensus rules
-fn parse_and_verify_vaa(deps: Deps, data: &[u8], block_time: u64) -> StdResult<ParsedVAA> {
-    let vaa = ParsedVAA::deserialize(data)?;
+/// active guardian set i.e. is valid according to Wormhole consensus rules. Also returns the
+/// guardians whose signatures were verified.
+fn parse_and_verify_vaa(
+    deps: Deps,
+    data: &[u8],
+    block_time: u64,
+) -> StdResult<(ParsedVAA, Vec<GuardianSigner>)> {
+    let vaa = VaaRef::parse(data)?;
+    let signers = verify_vaa(deps, &vaa, block_time)?;
+
+    Ok((vaa.into(), signers))
+}
 
-    if vaa.version != 1 {
+/// Verifies a borrowed VAA without copying any of its data and returns the signing guardians
+pub fn verify_vaa(deps: Deps, vaa: &VaaRef, block_time: u64) -> StdResult<Vec<GuardianSigner>> {
+    if vaa.version() != 1 {
         Err(StdError::generic_err("Invalid Version".to_string()))?
     }
 
+    verify_signatures(
+        deps,
+        &vaa.digest(),
+        vaa.signatures(),
+        vaa.guardian_set_index(),
+        block_time,
+    )
+}
+
+/// Verifies that a quorum of the guardian set signed `digest` and returns the signing guardians.
+/// The digest is the hash the guardians signed, for a VAA that is the double keccak256 of its
+/// body.
+pub fn verify_signatures<'a>(
+    deps: Deps,
+    digest: &VaaDigest,
+    signatures: impl ExactSizeIterator<Item = VaaSignature<'a>> + Clone,
+    guardian_set_index: u32,
+    block_time: u64,
+) -> StdResult<Vec<GuardianSigner>> {
     // first check guardian set index exist
-    if !GUARDIAN_SET.has(deps.storage, vaa.guardian_set_index) {
+    if !GUARDIAN_SET.has(deps.storage, guardian_set_index) {
         Err(StdError::generic_err(
             "Invalid guardian set index".to_string(),
         ))?
     }
 
     // Load and check guardian set
-    let guardian_set = GUARDIAN_SET.load(deps.storage, vaa.guardian_set_index)?;
+    let guardian_set = GUARDIAN_SET.load(deps.storage, guardian_set_index)?;
 
     if guardian_set.expiration_time != 0 && guardian_set.expiration_time < block_time {
         Err(StdError::generic_err("Guardian set expired".to_string()))?
     }
 
-    if (vaa.len_signers as usize) < guardian_set.quorum() {
+    let quorum_config = QUORUM_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+    let len_signers = signatures.len();
+    if len_signers < guardian_set.quorum(&quorum_config) {
         Err(StdError::generic_err("No quorum".to_string()))?
     }
 
-    // Verify guardian signatures
-    let mut last_index: i32 = -1;
-    let mut pos = ParsedVAA::HEADER_LEN;
-
-    for _ in 0..vaa.len_signers {
-        if pos + ParsedVAA::SIGNATURE_LEN > data.len() {
-            Err(StdError::generic_err("Invalid VAA".to_string()))?
-        }
+    if len_signers > guardian_set.addresses.len() {
+        Err(StdError::generic_err("Too many signatures".to_string()))?
+    }
 
-        let index = data.get_u8(pos) as i32;
+    // Structural pass over every signature before any key recovery, so junk VAAs are rejected
+    // without paying for ECDSA. Signature lengths are already exact, `VaaRef::parse` only accepts
+    // VAAs holding `len_signers` full signatures followed by a complete body.
+    let mut last_index: i32 = -1;
+    for sig in signatures.clone() {
+        let index = sig.index() as i32;
         if index <= last_index {
             Err(StdError::generic_err(
                 "Wrong guardian index order".to_string(),
@@ -173,53 +1046,122 @@ fn parse_and_verify_vaa(deps: Deps, data: &[u8], block_time: u64) -> StdResult<P
 
         last_index = index;
 
-        let signature = Signature::try_from(
-            &data[pos + ParsedVAA::SIG_DATA_POS
-                ..pos + ParsedVAA::SIG_DATA_POS + ParsedVAA::SIG_DATA_LEN],
-        )
-        .map_err(|_| StdError::generic_err("Cannot decode signature".to_string()))?;
+        if sig.index() as usize >= guardian_set.addresses.len() {
+            Err(StdError::generic_err("Too many signatures".to_string()))?
+        }
 
-        let recovery_id = RecoveryId::try_from(data.get_u8(pos + ParsedVAA::SIG_RECOVERY_POS))
-            .map_err(|_| StdError::generic_err("Cannot decode signature".to_string()))?;
+        // Recovery ids 2 and 3 only occur for an r overflowing the curve order, which honest
+        // signers never produce
+        if sig.recovery_id() > 1 {
+            Err(ContractError::InvalidRecoveryId {})?
+        }
+    }
 
-        let verify_key =
-            VerifyingKey::recover_from_prehash(vaa.hash.as_slice(), &signature, recovery_id)
-                .map_err(|_| StdError::generic_err("Cannot recover key".to_string()))?;
+    // Verify guardian signatures
+    let mut signers = Vec::with_capacity(len_signers);
 
-        let index = index as usize;
-        if index >= guardian_set.addresses.len() {
-            Err(StdError::generic_err("Too many signatures".to_string()))?
+    for sig in signatures {
+        let index = sig.index() as usize;
+
+        let signature = Signature::try_from(sig.signature())
+            .map_err(|_| StdError::generic_err("Cannot decode signature".to_string()))?;
+
+        // (r, n - s) is a second valid signature for the same digest, only accept low S
+        if signature.normalize_s().is_some() {
+            Err(ContractError::MalleableSignature {})?
         }
 
-        if !keys_equal(&verify_key, &guardian_set.addresses[index]) {
+        // Recovery runs in the host at a fixed gas cost per signature
+        let pubkey = deps
+            .api
+            .secp256k1_recover_pubkey(digest.as_slice(), sig.signature(), sig.recovery_id())
+            .map_err(|_| StdError::generic_err("Cannot recover key".to_string()))?;
+
+        if !pubkey_matches(&pubkey, &guardian_set.addresses[index]) {
             Err(StdError::generic_err(
                 "Guardian signature error".to_string(),
             ))?
         }
-        pos += ParsedVAA::SIGNATURE_LEN;
+
+        signers.push(GuardianSigner {
+            index: index as u8,
+            address: guardian_set.addresses[index].clone(),
+        });
     }
 
-    Ok(vaa)
+    Ok(signers)
 }
 
-fn vaa_update_guardian_set(
-    deps: DepsMut,
-    env: Env,
-    data: &[u8],
-) -> Result<Response, ContractError> {
+/// Rejects VAAs emitted with a weaker finality than configured for their emitter chain
+fn check_consistency_level(deps: Deps, vaa: &ParsedVAA) -> Result<(), ContractError> {
+    if let Some(min_finality) = MIN_CONSISTENCY_LEVEL.may_load(deps.storage, vaa.emitter_chain)? {
+        let finality = Finality::from_consistency_level(vaa.emitter_chain, vaa.consistency_level);
+        if finality < min_finality {
+            return Err(ContractError::InsufficientConsistencyLevel {});
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects VAAs whose timestamp is outside the freshness policy of their emitter chain. A chain
+/// specific policy takes precedence over the global one.
+fn check_freshness(deps: Deps, vaa: &ParsedVAA, block_time: u64) -> Result<(), ContractError> {
+    let policy = match load_freshness_policy(deps, vaa.emitter_chain)? {
+        Some(policy) => policy,
+        None => return Ok(()),
+    };
+
+    let timestamp = vaa.timestamp as u64;
+    if let Some(max_age) = policy.max_age {
+        if block_time > timestamp.saturating_add(max_age) {
+            return Err(ContractError::VaaTooOld {});
+        }
+    }
+    if let Some(max_future_drift) = policy.max_future_drift {
+        if timestamp > block_time.saturating_add(max_future_drift) {
+            return Err(ContractError::VaaTimestampInFuture {});
+        }
+    }
+
+    Ok(())
+}
+
+fn load_freshness_policy(deps: Deps, emitter_chain: u16) -> StdResult<Option<FreshnessPolicy>> {
+    match CHAIN_FRESHNESS_POLICY.may_load(deps.storage, emitter_chain)? {
+        Some(policy) => Ok(Some(policy)),
+        None => FRESHNESS_POLICY.may_load(deps.storage),
+    }
+}
+
+fn vaa_update_guardian_set(deps: DepsMut, env: Env, data: &[u8]) -> GovernanceResult {
     /* Payload format
     0   uint32 new_index
     4   uint8 len(keys)
     5   [][20]uint8 guardian addresses
     */
 
-    let mut config = CONFIG.load(deps.storage)?;
-
     let GuardianSetUpgrade {
         new_guardian_set_index,
         new_guardian_set,
     } = GuardianSetUpgrade::deserialize(data)?;
 
+    update_guardian_set(deps, env, new_guardian_set_index, new_guardian_set)
+}
+
+/// Installs the next guardian set and starts the expiry of the current one, shared by guardian
+/// governance and the chain governance sudo recovery
+fn update_guardian_set(
+    deps: DepsMut,
+    env: Env,
+    new_guardian_set_index: u32,
+    new_guardian_set: GuardianSetInfo,
+) -> GovernanceResult {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    let quorum_config = QUORUM_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+    new_guardian_set.validate(&quorum_config)?;
+
     if new_guardian_set_index != config.guardian_set_index + 1 {
         return Err(ContractError::GuardianSetIndexIncreaseError {});
     }
@@ -231,38 +1173,2765 @@ fn vaa_update_guardian_set(
     GUARDIAN_SET.save(deps.storage, config.guardian_set_index, &new_guardian_set)?;
     CONFIG.save(deps.storage, &config)?;
 
-    GUARDIAN_SET.update(deps.storage, old_guardian_set_index, |op| match op {
-        None => Err(ContractError::InvalidGuardianSetIndex {}),
-        Some(mut old_gs) => {
-            old_gs.expiration_time = env.block.time.seconds() + config.guardian_set_expirity;
+    let old_guardian_set =
+        GUARDIAN_SET.update(deps.storage, old_guardian_set_index, |op| match op {
+            None => Err(ContractError::InvalidGuardianSetIndex {}),
+            Some(mut old_gs) => {
+                old_gs.expiration_time = env.block.time.seconds() + config.guardian_set_expirity;
 
-            Ok(old_gs)
-        }
-    })?;
+                Ok(old_gs)
+            }
+        })?;
 
-    Ok(Response::new()
-        .add_attribute("action", "guardian_set_change")
+    let quorum = new_guardian_set.quorum(&quorum_config);
+    let addresses = new_guardian_set
+        .addresses
+        .iter()
+        .map(|address| hex::encode(address.bytes.as_slice()))
+        .collect::<Vec<_>>()
+        .join(",");
+    let log = GovernanceLog::new(
+        "guardian_set_change",
+        vec![
+            attr("new_guardian_set_index", new_guardian_set_index.to_string()),
+            attr("addresses", addresses),
+            attr("quorum", quorum.to_string()),
+        ],
+    );
+
+    let event = GuardianSetUpgradeEvent {
+        old_guardian_set_index,
+        new_guardian_set_index: config.guardian_set_index,
+        addresses: &new_guardian_set.addresses,
+        quorum,
+        old_guardian_set_expiration: old_guardian_set.expiration_time,
+    };
+
+    let res = Response::new()
+        .add_attribute("action", log.name)
         .add_attribute("old", old_guardian_set_index.to_string())
-        .add_attribute("new", config.guardian_set_index.to_string()))
+        .add_attribute("new", config.guardian_set_index.to_string())
+        .add_event(event);
+    Ok((res, log))
 }
 
-pub fn query_guardian_set_info(deps: Deps) -> StdResult<GuardianSetInfoResponse> {
-    let config = CONFIG.load(deps.storage)?;
-    let guardian_set = GUARDIAN_SET.load(deps.storage, config.guardian_set_index)?;
+fn vaa_set_consistency_level(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let SetConsistencyLevel {
+        emitter_chain,
+        min_finality,
+    } = SetConsistencyLevel::deserialize(data)?;
 
-    let res = GuardianSetInfoResponse {
-        guardian_set_index: config.guardian_set_index,
-        addresses: guardian_set.addresses,
-    };
+    // Instant finality is the weakest level, requiring it is the same as having no policy
+    if min_finality == Finality::Instant {
+        MIN_CONSISTENCY_LEVEL.remove(deps.storage, emitter_chain);
+    } else {
+        MIN_CONSISTENCY_LEVEL.save(deps.storage, emitter_chain, &min_finality)?;
+    }
 
-    Ok(res)
+    GovernanceLog::new(
+        "set_consistency_level",
+        vec![
+            attr("emitter_chain", emitter_chain.to_string()),
+            attr("min_finality", format!("{:?}", min_finality)),
+        ],
+    )
+    .into_response()
 }
 
-pub fn query_address_hex(deps: Deps, address: &HumanAddr) -> StdResult<GetAddressHexResponse> {
-    Ok(GetAddressHexResponse {
-        hex: hex::encode(extend_address_to_32(&deps.api.addr_canonicalize(address)?)),
-    })
+fn vaa_set_freshness_policy(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let SetFreshnessPolicy {
+        emitter_chain,
+        policy,
+    } = SetFreshnessPolicy::deserialize(data)?;
+
+    let unrestricted = policy.max_age.is_none() && policy.max_future_drift.is_none();
+    match (emitter_chain, unrestricted) {
+        (0, true) => FRESHNESS_POLICY.remove(deps.storage),
+        (0, false) => FRESHNESS_POLICY.save(deps.storage, &policy)?,
+        (_, true) => CHAIN_FRESHNESS_POLICY.remove(deps.storage, emitter_chain),
+        (_, false) => CHAIN_FRESHNESS_POLICY.save(deps.storage, emitter_chain, &policy)?,
+    }
+
+    let format_bound = |bound: Option<u64>| bound.map_or("none".to_string(), |b| b.to_string());
+    GovernanceLog::new(
+        "set_freshness_policy",
+        vec![
+            attr("emitter_chain", emitter_chain.to_string()),
+            attr("max_age", format_bound(policy.max_age)),
+            attr("max_future_drift", format_bound(policy.max_future_drift)),
+        ],
+    )
+    .into_response()
 }
 
-#[cfg(test)]
-mod tests {}
+fn vaa_register_emitter(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let RegisterEmitter {
+        emitter_chain,
+        emitter_address,
+        receiver,
+        strict_ordering,
+        token_bridge,
+    } = RegisterEmitter::deserialize(data)?;
+
+    let key = (emitter_chain, emitter_address.as_slice());
+    let receiver = match receiver {
+        Some(receiver) => {
+            let receiver = deps.api.addr_humanize(&receiver)?;
+            EMITTER_REGISTRATIONS.save(
+                deps.storage,
+                key,
+                &EmitterRegistration {
+                    receiver: receiver.clone(),
+                    strict_ordering,
+                    token_bridge,
+                },
+            )?;
+            receiver.into_string()
+        }
+        None => {
+            EMITTER_REGISTRATIONS.remove(deps.storage, key);
+            String::new()
+        }
+    };
+
+    GovernanceLog::new(
+        "register_emitter",
+        vec![
+            attr("emitter_chain", emitter_chain.to_string()),
+            attr("emitter_address", emitter_address.to_string()),
+            attr("receiver", receiver),
+            attr("strict_ordering", strict_ordering.to_string()),
+            attr("token_bridge", token_bridge.to_string()),
+        ],
+    )
+    .into_response()
+}
+
+/// Sets how long an old guardian set stays valid after future rotations, sets that are
+/// already expiring keep their expiration
+fn vaa_set_guardian_set_expiry(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let SetGuardianSetExpiry {
+        guardian_set_expirity,
+    } = SetGuardianSetExpiry::deserialize(data)?;
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.guardian_set_expirity = guardian_set_expirity;
+        Ok(config)
+    })?;
+
+    GovernanceLog::new(
+        "set_guardian_set_expiry",
+        vec![attr(
+            "guardian_set_expirity",
+            guardian_set_expirity.to_string(),
+        )],
+    )
+    .into_response()
+}
+
+/// Expires or extends an old guardian set
+fn vaa_set_guardian_set_expiration(deps: DepsMut, env: Env, data: &[u8]) -> GovernanceResult {
+    let SetGuardianSetExpiration {
+        guardian_set_index,
+        expiration_time,
+    } = SetGuardianSetExpiration::deserialize(data)?;
+
+    let expiration_time =
+        update_guardian_set_expiration(deps, &env, guardian_set_index, expiration_time, true)?;
+
+    GovernanceLog::new(
+        "set_guardian_set_expiration",
+        vec![
+            attr("guardian_set_index", guardian_set_index.to_string()),
+            attr("expiration_time", expiration_time.to_string()),
+        ],
+    )
+    .into_response()
+}
+
+/// Sets the expiration time of an old guardian set, 0 expires it immediately as stored 0 would
+/// otherwise mean the set never expires. Returns the stored expiration time.
+fn update_guardian_set_expiration(
+    deps: DepsMut,
+    env: &Env,
+    guardian_set_index: u32,
+    expiration_time: u64,
+    allow_extension: bool,
+) -> Result<u64, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if guardian_set_index == config.guardian_set_index {
+        return Err(ContractError::CannotExpireCurrentGuardianSet {});
+    }
+
+    // Sets expire once the block time is past their expiration time
+    let expiration_time = match expiration_time {
+        0 => env.block.time.seconds().saturating_sub(1),
+        expiration_time => expiration_time,
+    };
+
+    GUARDIAN_SET.update(deps.storage, guardian_set_index, |op| match op {
+        None => Err(ContractError::InvalidGuardianSetIndex {}),
+        Some(mut guardian_set) => {
+            if !allow_extension && expiration_time >= guardian_set.expiration_time {
+                return Err(ContractError::ExpirationNotShortened {});
+            }
+            guardian_set.expiration_time = expiration_time;
+            Ok(guardian_set)
+        }
+    })?;
+
+    Ok(expiration_time)
+}
+
+fn vaa_set_paused(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let SetPaused { paused } = SetPaused::deserialize(data)?;
+    PAUSED.save(deps.storage, &paused)?;
+
+    GovernanceLog::new("set_paused", vec![attr("paused", paused.to_string())]).into_response()
+}
+
+/// Removes the admin. Pauses and expirations set by the admin are reverted with actions 133
+/// and 132.
+fn vaa_revoke_admin(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    RevokeAdmin::deserialize(data)?;
+
+    let admin = remove_admin(deps.storage)?;
+
+    GovernanceLog::new(
+        "revoke_admin",
+        vec![attr(
+            "admin",
+            admin.map(Addr::into_string).unwrap_or_default(),
+        )],
+    )
+    .into_response()
+}
+
+/// Limits are checked against the usage of the current window, already queued VAAs keep their
+/// release time
+fn vaa_set_governor_limit(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let SetGovernorLimit {
+        emitter_chain,
+        limit,
+    } = SetGovernorLimit::deserialize(data)?;
+
+    if limit.max_vaas.is_none() && limit.max_notional.is_none() {
+        GOVERNOR_LIMITS.remove(deps.storage, emitter_chain);
+    } else {
+        GOVERNOR_LIMITS.save(deps.storage, emitter_chain, &limit)?;
+    }
+
+    let format_bound = |bound: Option<String>| bound.unwrap_or_else(|| "none".to_string());
+    GovernanceLog::new(
+        "set_governor_limit",
+        vec![
+            attr("emitter_chain", emitter_chain.to_string()),
+            attr(
+                "max_vaas",
+                format_bound(limit.max_vaas.map(|v| v.to_string())),
+            ),
+            attr(
+                "max_notional",
+                format_bound(limit.max_notional.map(|v| v.to_string())),
+            ),
+            attr("release_delay", limit.release_delay.to_string()),
+        ],
+    )
+    .into_response()
+}
+
+/// Prices are only used for new transfers, queued VAAs keep the value they were queued with
+fn vaa_set_token_price(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let SetTokenPrice {
+        token_chain,
+        token_address,
+        price,
+    } = SetTokenPrice::deserialize(data)?;
+
+    let key = (token_chain, token_address.as_slice());
+    match price {
+        Some(price) => TOKEN_PRICES.save(deps.storage, key, &price)?,
+        None => TOKEN_PRICES.remove(deps.storage, key),
+    }
+
+    GovernanceLog::new(
+        "set_token_price",
+        vec![
+            attr("token_chain", token_chain.to_string()),
+            attr("token_address", token_address.to_string()),
+            attr(
+                "price",
+                price.map_or("none".to_string(), |price| price.to_string()),
+            ),
+        ],
+    )
+    .into_response()
+}
+
+/// Releases a queued VAA before its release time
+fn vaa_release_pending_vaa(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let ReleasePendingVAA { digest } = ReleasePendingVAA::deserialize(data)?;
+
+    let res = release_pending_vaa(deps.storage, &digest)?;
+    let log = GovernanceLog::new(
+        "release_pending_vaa",
+        vec![attr("digest", digest.to_string())],
+    );
+    Ok((res.add_attribute("released_by", "governance"), log))
+}
+
+/// Delay of timelocked governance actions, 0 applies them immediately. Actions already queued
+/// keep their execution time.
+fn vaa_set_governance_delay(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let SetGovernanceDelay { delay } = SetGovernanceDelay::deserialize(data)?;
+
+    if delay == 0 {
+        GOVERNANCE_DELAY.remove(deps.storage);
+    } else {
+        GOVERNANCE_DELAY.save(deps.storage, &delay)?;
+    }
+
+    GovernanceLog::new(
+        "set_governance_delay",
+        vec![attr("delay", delay.to_string())],
+    )
+    .into_response()
+}
+
+fn vaa_add_governance_emitter(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let UpdateGovernanceEmitter { emitter } = UpdateGovernanceEmitter::deserialize(data)?;
+
+    GOVERNANCE_EMITTERS.save(
+        deps.storage,
+        (emitter.emitter_chain, emitter.emitter_address.as_slice()),
+        &Empty {},
+    )?;
+
+    GovernanceLog::new(
+        "add_governance_emitter",
+        vec![
+            attr("emitter_chain", emitter.emitter_chain.to_string()),
+            attr("emitter_address", emitter.emitter_address.to_string()),
+        ],
+    )
+    .into_response()
+}
+
+/// Drops a queued action, e.g. one that can no longer be executed. Cancelling is never
+/// timelocked.
+fn vaa_cancel_pending_governance(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let CancelPendingGovernance { id } = CancelPendingGovernance::deserialize(data)?;
+
+    let pending = PENDING_GOVERNANCE
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::PendingActionNotFound {})?;
+    PENDING_GOVERNANCE.remove(deps.storage, id);
+
+    GovernanceLog::new(
+        "cancel_pending_governance",
+        vec![
+            attr("id", id.to_string()),
+            attr("governance_action", pending.action.to_string()),
+        ],
+    )
+    .into_response()
+}
+
+/// Governance can move to another emitter, but never remove the last one. Actions queued by
+/// the removed emitter are dropped with it.
+fn vaa_remove_governance_emitter(deps: DepsMut, data: &[u8]) -> GovernanceResult {
+    let UpdateGovernanceEmitter { emitter } = UpdateGovernanceEmitter::deserialize(data)?;
+
+    let key = (emitter.emitter_chain, emitter.emitter_address.as_slice());
+    if !GOVERNANCE_EMITTERS.has(deps.storage, key) {
+        return Err(ContractError::GovernanceEmitterNotFound {});
+    }
+    let remaining = GOVERNANCE_EMITTERS
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .take(2)
+        .count();
+    if remaining < 2 {
+        return Err(ContractError::LastGovernanceEmitter {});
+    }
+    GOVERNANCE_EMITTERS.remove(deps.storage, key);
+
+    let dropped = PENDING_GOVERNANCE
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((id, pending)) if pending.emitter == emitter => Some(Ok(id)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    for id in &dropped {
+        PENDING_GOVERNANCE.remove(deps.storage, *id);
+    }
+
+    GovernanceLog::new(
+        "remove_governance_emitter",
+        vec![
+            attr("emitter_chain", emitter.emitter_chain.to_string()),
+            attr("emitter_address", emitter.emitter_address.to_string()),
+            attr("dropped_pending", dropped.len().to_string()),
+        ],
+    )
+    .into_response()
+}
+
+fn ensure_admin(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    match OWNERSHIP
+        .may_load(deps.storage)?
+        .and_then(|ownership| ownership.owner)
+    {
+        Some(admin) if admin == info.sender => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+/// Removes the admin, any pending transfer and the submitter restriction, which nobody could
+/// lift afterwards. Returns the removed admin.
+fn remove_admin(storage: &mut dyn Storage) -> StdResult<Option<Addr>> {
+    let ownership = OWNERSHIP.may_load(storage)?.unwrap_or_default();
+    OWNERSHIP.remove(storage);
+    ALLOWED_SUBMITTERS.remove(storage);
+
+    Ok(ownership.owner)
+}
+
+fn handle_update_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: OwnershipAction,
+) -> Result<Response, ContractError> {
+    let mut ownership = OWNERSHIP.may_load(deps.storage)?.unwrap_or_default();
+
+    let res = match action {
+        OwnershipAction::TransferOwnership { new_owner, expiry } => {
+            ensure_admin(deps.as_ref(), &info)?;
+            if expiry.is_some_and(|expiry| expiry.is_expired(&env.block)) {
+                return Err(ContractError::OwnershipTransferExpired {});
+            }
+
+            let new_owner = deps.api.addr_validate(&new_owner)?;
+            ownership.pending_owner = Some(new_owner.clone());
+            ownership.pending_expiry = expiry;
+            Response::new()
+                .add_attribute("action", "transfer_ownership")
+                .add_attribute("pending_owner", new_owner)
+                .add_attribute(
+                    "pending_expiry",
+                    expiry.map_or("none".to_string(), |expiry| expiry.to_string()),
+                )
+        }
+        OwnershipAction::AcceptOwnership => {
+            match &ownership.pending_owner {
+                None => return Err(ContractError::OwnershipTransferNotFound {}),
+                Some(pending_owner) if *pending_owner != info.sender => {
+                    return Err(ContractError::Unauthorized {})
+                }
+                Some(_) => {}
+            }
+            if ownership
+                .pending_expiry
+                .is_some_and(|expiry| expiry.is_expired(&env.block))
+            {
+                return Err(ContractError::OwnershipTransferExpired {});
+            }
+
+            ownership = Ownership {
+                owner: Some(info.sender.clone()),
+                ..Ownership::default()
+            };
+            Response::new().add_attribute("action", "accept_ownership")
+        }
+        OwnershipAction::RenounceOwnership => {
+            ensure_admin(deps.as_ref(), &info)?;
+            remove_admin(deps.storage)?;
+
+            return Ok(Response::new()
+                .add_attribute("action", "renounce_ownership")
+                .add_attribute("sender", info.sender));
+        }
+        OwnershipAction::CancelOwnershipTransfer => {
+            ensure_admin(deps.as_ref(), &info)?;
+            if ownership.pending_owner.is_none() {
+                return Err(ContractError::OwnershipTransferNotFound {});
+            }
+
+            ownership.pending_owner = None;
+            ownership.pending_expiry = None;
+            Response::new().add_attribute("action", "cancel_ownership_transfer")
+        }
+    };
+    OWNERSHIP.save(deps.storage, &ownership)?;
+
+    Ok(res.add_attribute("sender", info.sender))
+}
+
+fn handle_pause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    ensure_admin(deps.as_ref(), &info)?;
+    PAUSED.save(deps.storage, &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "admin_pause")
+        .add_attribute("admin", info.sender))
+}
+
+fn handle_shorten_guardian_set_expiration(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    guardian_set_index: u32,
+    expiration_time: u64,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.as_ref(), &info)?;
+    let expiration_time =
+        update_guardian_set_expiration(deps, &env, guardian_set_index, expiration_time, false)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "admin_shorten_guardian_set_expiration")
+        .add_attribute("admin", info.sender)
+        .add_attribute("guardian_set_index", guardian_set_index.to_string())
+        .add_attribute("expiration_time", expiration_time.to_string()))
+}
+
+fn handle_restrict_submitters(
+    deps: DepsMut,
+    info: MessageInfo,
+    submitters: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.as_ref(), &info)?;
+
+    let submitters = match submitters {
+        Some(submitters) => {
+            let submitters = submitters
+                .iter()
+                .map(|submitter| deps.api.addr_validate(submitter))
+                .collect::<StdResult<Vec<_>>>()?;
+            ALLOWED_SUBMITTERS.save(deps.storage, &submitters)?;
+            submitters
+                .iter()
+                .map(Addr::as_str)
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+        None => {
+            ALLOWED_SUBMITTERS.remove(deps.storage);
+            "any".to_string()
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "admin_restrict_submitters")
+        .add_attribute("admin", info.sender)
+        .add_attribute("submitters", submitters))
+}
+
+pub fn query_guardian_set_info(deps: Deps) -> StdResult<GuardianSetInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let guardian_set = GUARDIAN_SET.load(deps.storage, config.guardian_set_index)?;
+
+    let res = GuardianSetInfoResponse {
+        guardian_set_index: config.guardian_set_index,
+        addresses: guardian_set.addresses,
+    };
+
+    Ok(res)
+}
+
+pub fn query_verify_vaa(
+    deps: Deps,
+    data: &[u8],
+    block_time: u64,
+    strict: bool,
+) -> StdResult<VerifyVAAResponse> {
+    let (vaa, signers) = parse_and_verify_vaa(deps, data, block_time)?;
+    if strict {
+        check_consistency_level(deps, &vaa)?;
+        check_freshness(deps, &vaa, block_time)?;
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let guardian_set = GUARDIAN_SET.load(deps.storage, vaa.guardian_set_index)?;
+
+    let mut res = VerifyVAAResponse::from(vaa);
+    res.guardian_set_active = res.guardian_set_index == config.guardian_set_index;
+    res.guardian_set_expiration_time = guardian_set.expiration_time;
+    res.signers = signers;
+
+    Ok(res)
+}
+
+pub fn query_verify_vaa_with_threshold(
+    deps: Deps,
+    data: &[u8],
+    block_time: u64,
+    min_signatures: u32,
+) -> StdResult<VerifyVAAResponse> {
+    let res = query_verify_vaa(deps, data, block_time, false)?;
+    if res.signers.len() < min_signatures as usize {
+        return Err(ContractError::ThresholdNotMet {}.into());
+    }
+
+    Ok(res)
+}
+
+pub fn query_verify_signatures(
+    deps: Deps,
+    digest: &VaaDigest,
+    signatures: &[GuardianSignature],
+    guardian_set_index: u32,
+    block_time: u64,
+) -> StdResult<VerifySignaturesResponse> {
+    let signatures = signatures
+        .iter()
+        .map(|sig| VaaSignature::new(sig.index, &sig.signature))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let signers = verify_signatures(
+        deps,
+        digest,
+        signatures.into_iter(),
+        guardian_set_index,
+        block_time,
+    )?;
+
+    Ok(VerifySignaturesResponse { signers })
+}
+
+/// Query responses are only accepted from the current guardian set
+pub fn query_verify_query_response(
+    deps: Deps,
+    block_time: u64,
+    data: &[u8],
+    signatures: &[GuardianSignature],
+) -> StdResult<VerifyQueryResponseResponse> {
+    let response = QueryResponse::deserialize(data)?;
+
+    let signatures = signatures
+        .iter()
+        .map(|sig| VaaSignature::new(sig.index, &sig.signature))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let signers = verify_signatures(
+        deps,
+        &query_response_digest(data),
+        signatures.into_iter(),
+        config.guardian_set_index,
+        block_time,
+    )?;
+
+    Ok(VerifyQueryResponseResponse { response, signers })
+}
+
+pub fn query_quorum(deps: Deps) -> StdResult<QuorumResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let guardian_set = GUARDIAN_SET.load(deps.storage, config.guardian_set_index)?;
+    let quorum_config = QUORUM_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(QuorumResponse {
+        rule: quorum_config.rule,
+        test_mode: quorum_config.test_mode,
+        quorum: guardian_set.quorum(&quorum_config) as u32,
+    })
+}
+
+pub fn query_consistency_level(
+    deps: Deps,
+    emitter_chain: u16,
+) -> StdResult<ConsistencyLevelResponse> {
+    Ok(ConsistencyLevelResponse {
+        emitter_chain,
+        min_finality: MIN_CONSISTENCY_LEVEL.may_load(deps.storage, emitter_chain)?,
+    })
+}
+
+pub fn query_freshness_policy(
+    deps: Deps,
+    emitter_chain: u16,
+) -> StdResult<FreshnessPolicyResponse> {
+    Ok(FreshnessPolicyResponse {
+        emitter_chain,
+        policy: load_freshness_policy(deps, emitter_chain)?,
+    })
+}
+
+pub fn query_emitter_registration(
+    deps: Deps,
+    emitter_chain: u16,
+    emitter_address: &[u8],
+) -> StdResult<EmitterRegistrationResponse> {
+    Ok(EmitterRegistrationResponse {
+        registration: EMITTER_REGISTRATIONS
+            .may_load(deps.storage, (emitter_chain, emitter_address))?,
+    })
+}
+
+pub fn query_last_sequence(
+    deps: Deps,
+    emitter_chain: u16,
+    emitter_address: &[u8],
+) -> StdResult<LastSequenceResponse> {
+    Ok(LastSequenceResponse {
+        sequence: LAST_SEQUENCE.may_load(deps.storage, (emitter_chain, emitter_address))?,
+    })
+}
+
+pub fn query_missing_sequences(
+    deps: Deps,
+    emitter_chain: u16,
+    emitter_address: &[u8],
+    start: u64,
+    end: u64,
+) -> StdResult<MissingSequencesResponse> {
+    if start >= end || end - start > MAX_SEQUENCE_RANGE {
+        return Err(ContractError::InvalidSequenceRange {}.into());
+    }
+
+    let mut missing = vec![];
+    for bucket in start / 64..=(end - 1) / 64 {
+        let bitmap = SEEN_SEQUENCES
+            .may_load(deps.storage, (emitter_chain, emitter_address, bucket))?
+            .unwrap_or_default();
+        let first = u64::max(start, bucket * 64);
+        let last = u64::min(end, bucket.saturating_mul(64).saturating_add(64));
+        missing.extend((first..last).filter(|seq| bitmap & 1 << (seq % 64) == 0));
+    }
+
+    Ok(MissingSequencesResponse { missing })
+}
+
+pub fn query_vaa_status(
+    deps: Deps,
+    emitter_chain: u16,
+    emitter_address: &[u8],
+    sequence: u64,
+) -> StdResult<VAAStatusResponse> {
+    Ok(VAAStatusResponse {
+        consumed: CONSUMED_VAA_BY_MESSAGE_ID
+            .may_load(deps.storage, (emitter_chain, emitter_address, sequence))?,
+    })
+}
+
+pub fn query_vaa_receipt(deps: Deps, digest: &[u8]) -> StdResult<VAAReceiptResponse> {
+    Ok(VAAReceiptResponse {
+        receipt: CONSUMED_VAA_ARCHIVE.may_load(deps.storage, digest)?,
+    })
+}
+
+pub fn query_governor_usage(
+    deps: Deps,
+    block_time: u64,
+    emitter_chain: u16,
+) -> StdResult<GovernorUsageResponse> {
+    let usage = governor_usage(deps.storage, emitter_chain, block_time)?;
+
+    Ok(GovernorUsageResponse {
+        limit: GOVERNOR_LIMITS.may_load(deps.storage, emitter_chain)?,
+        vaas: usage.vaas,
+        notional: usage.notional,
+    })
+}
+
+pub fn query_token_price(
+    deps: Deps,
+    token_chain: u16,
+    token_address: &[u8],
+) -> StdResult<TokenPriceResponse> {
+    Ok(TokenPriceResponse {
+        price: TOKEN_PRICES.may_load(deps.storage, (token_chain, token_address))?,
+    })
+}
+
+pub fn query_pending_vaas(
+    deps: Deps,
+    start_after: Option<VaaDigest>,
+    limit: Option<u32>,
+) -> StdResult<PendingVAAsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .as_ref()
+        .map(|digest| Bound::exclusive(digest.as_slice()));
+
+    let pending = PENDING_VAAS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, pending)| pending))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PendingVAAsResponse { pending })
+}
+
+pub fn query_pending_governance_actions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PendingGovernanceActionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let actions = PENDING_GOVERNANCE
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, pending)| pending))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PendingGovernanceActionsResponse {
+        delay: GOVERNANCE_DELAY.may_load(deps.storage)?.unwrap_or_default(),
+        actions,
+    })
+}
+
+pub fn query_governance_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GovernanceHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let entries = GOVERNANCE_HISTORY
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, entry)| entry))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GovernanceHistoryResponse { entries })
+}
+
+pub fn query_governance_emitters(deps: Deps) -> StdResult<GovernanceEmittersResponse> {
+    let emitters = GOVERNANCE_EMITTERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|key| {
+            let (emitter_chain, emitter_address) = key?;
+            Ok(GovernanceEmitter {
+                emitter_chain,
+                emitter_address: ExternalAddress::try_from(emitter_address.as_slice())?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GovernanceEmittersResponse { emitters })
+}
+
+pub fn query_ownership(deps: Deps) -> StdResult<Ownership> {
+    Ok(OWNERSHIP.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn query_allowed_submitters(deps: Deps) -> StdResult<AllowedSubmittersResponse> {
+    Ok(AllowedSubmittersResponse {
+        submitters: ALLOWED_SUBMITTERS.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+    Ok(PauseStatusResponse {
+        paused: PAUSED.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+pub fn query_address_hex(deps: Deps, address: &HumanAddr) -> StdResult<GetAddressHexResponse> {
+    Ok(GetAddressHexResponse {
+        hex: hex::encode(extend_address_to_32(&deps.api.addr_canonicalize(address)?)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::{ChainFreshnessPolicy, ConsistencyLevelPolicy};
+    use crate::state::LegacyConfigInfo;
+    use crate::testing::*;
+    use cosmwasm_schema::schemars::schema::{InstanceType, Schema};
+    use cosmwasm_schema::{schema_for, QueryResponses};
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        attr, from_json, to_json_string, Api, CanonicalAddr, CosmosMsg, Event, OwnedDeps,
+        QuerierWrapper, RecoverPubkeyError, VerificationError,
+    };
+    use cw_utils::Expiration;
+    use sha3::{Digest, Keccak256};
+    use std::cell::Cell;
+
+    type MockDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+    fn setup(msg: InstantiateMsg) -> MockDeps {
+        let mut deps = mock_dependencies();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        deps
+    }
+
+    fn submit_vaa(deps: &mut MockDeps, vaa: Vec<u8>) -> Result<Response, ContractError> {
+        let info = message_info(&deps.api.addr_make("submitter"), &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SubmitVAA { vaa: vaa.into() },
+        )
+    }
+
+    /// Submits a governance VAA signed by the first three guardians of `guardian_set_index`
+    fn submit_governance(
+        deps: &mut MockDeps,
+        guardian_set_index: u32,
+        sequence: u64,
+        action: u8,
+        payload: &[u8],
+    ) -> Result<Response, ContractError> {
+        let mut body = Body::governance(action, payload);
+        body.sequence = sequence;
+        submit_vaa(deps, sign_vaa(guardian_set_index, &[0, 1, 2], &body))
+    }
+
+    fn verify_query(deps: &MockDeps, vaa: Vec<u8>, strict: bool) -> StdResult<VerifyVAAResponse> {
+        let msg = QueryMsg::VerifyVAA {
+            vaa: vaa.into(),
+            block_time: mock_env().block.time.seconds(),
+            strict: Some(strict),
+        };
+        query(deps.as_ref(), mock_env(), msg).and_then(from_json)
+    }
+
+    #[test]
+    fn consistency_level_enforced_in_strict_mode() {
+        let mut msg = instantiate_msg(4);
+        msg.consistency_levels = vec![ConsistencyLevelPolicy {
+            emitter_chain: 2,
+            min_finality: Finality::Finalized,
+        }];
+        let deps = setup(msg);
+
+        let instant = sign_vaa(0, &[0, 1, 2], &Body::message(2, 200));
+        verify_query(&deps, instant.clone(), false).unwrap();
+        let err = verify_query(&deps, instant, true).unwrap_err();
+        assert!(err.to_string().contains("InsufficientConsistencyLevel"));
+
+        let finalized = sign_vaa(0, &[0, 1, 2], &Body::message(2, 1));
+        verify_query(&deps, finalized, true).unwrap();
+
+        // Other chains are not restricted
+        let other = sign_vaa(0, &[0, 1, 2], &Body::message(3, 200));
+        verify_query(&deps, other, true).unwrap();
+    }
+
+    #[test]
+    fn consistency_level_set_by_governance() {
+        let mut deps = setup(instantiate_msg(4));
+
+        let mut payload = 2u16.to_be_bytes().to_vec();
+        payload.push(1);
+        let gov = sign_vaa(0, &[0, 1, 2], &Body::governance(128, &payload));
+        submit_vaa(&mut deps, gov).unwrap();
+
+        let res: ConsistencyLevelResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ConsistencyLevel { emitter_chain: 2 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.min_finality, Some(Finality::Safe));
+
+        let instant = sign_vaa(0, &[0, 1, 2], &Body::message(2, 200));
+        assert!(verify_query(&deps, instant, true).is_err());
+        let safe = sign_vaa(0, &[0, 1, 2], &Body::message(2, 201));
+        verify_query(&deps, safe, true).unwrap();
+    }
+
+    #[test]
+    fn freshness_enforced_in_strict_mode() {
+        let now = mock_env().block.time.seconds() as u32;
+        let mut msg = instantiate_msg(4);
+        msg.freshness_policy = Some(FreshnessPolicy {
+            max_age: Some(3600),
+            max_future_drift: Some(60),
+        });
+        msg.chain_freshness_policies = vec![ChainFreshnessPolicy {
+            emitter_chain: 3,
+            policy: FreshnessPolicy {
+                max_age: None,
+                max_future_drift: None,
+            },
+        }];
+        let deps = setup(msg);
+
+        let vaa_at = |emitter_chain: u16, timestamp: u32| {
+            let mut body = Body::message(emitter_chain, 1);
+            body.timestamp = timestamp;
+            sign_vaa(0, &[0, 1, 2], &body)
+        };
+
+        verify_query(&deps, vaa_at(2, now - 3600), true).unwrap();
+        verify_query(&deps, vaa_at(2, now - 3601), false).unwrap();
+        let err = verify_query(&deps, vaa_at(2, now - 3601), true).unwrap_err();
+        assert!(err.to_string().contains("VaaTooOld"));
+        let err = verify_query(&deps, vaa_at(2, now + 61), true).unwrap_err();
+        assert!(err.to_string().contains("VaaTimestampInFuture"));
+
+        // The chain specific policy overrides the global one
+        verify_query(&deps, vaa_at(3, 0), true).unwrap();
+    }
+
+    fn register_emitter(deps: &mut MockDeps, body: &Body, strict_ordering: bool) -> Addr {
+        register_emitter_with(deps, body, strict_ordering, false)
+    }
+
+    fn register_token_bridge(deps: &mut MockDeps, body: &Body, strict_ordering: bool) -> Addr {
+        register_emitter_with(deps, body, strict_ordering, true)
+    }
+
+    fn register_emitter_with(
+        deps: &mut MockDeps,
+        body: &Body,
+        strict_ordering: bool,
+        token_bridge: bool,
+    ) -> Addr {
+        let receiver = deps.api.addr_make("receiver");
+        let canonical = deps.api.addr_canonicalize(receiver.as_str()).unwrap();
+        let mut payload = body.emitter_chain.to_be_bytes().to_vec();
+        payload.extend_from_slice(&body.emitter_address);
+        payload.extend_from_slice(&extend_address_to_32(&canonical));
+        payload.push(strict_ordering as u8);
+        payload.push(token_bridge as u8);
+        let gov = Body::governance(130, &payload);
+        submit_vaa(deps, sign_vaa(0, &[0, 1, 2], &gov)).unwrap();
+        receiver
+    }
+
+    fn message_with_sequence(sequence: u64) -> Body {
+        let mut body = Body::message(2, 1);
+        body.sequence = sequence;
+        body
+    }
+
+    #[test]
+    fn registered_emitter_receives_vaas_and_tracks_gaps() {
+        let mut deps = setup(instantiate_msg(4));
+        let emitter = message_with_sequence(0);
+        let receiver = register_emitter(&mut deps, &emitter, false);
+
+        for sequence in [0, 1, 3, 6] {
+            let res = submit_vaa(
+                &mut deps,
+                sign_vaa(0, &[0, 1, 2], &message_with_sequence(sequence)),
+            )
+            .unwrap();
+            assert_eq!(res.messages.len(), 1);
+            assert!(matches!(
+                &res.messages[0].msg,
+                CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) if contract_addr == receiver.as_str()
+            ));
+        }
+
+        let err = submit_vaa(
+            &mut deps,
+            sign_vaa(0, &[0, 1, 2], &message_with_sequence(3)),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::VaaAlreadyExecuted {}));
+
+        let emitter_address = ExternalAddress(emitter.emitter_address);
+        let last: LastSequenceResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::LastSequence {
+                    emitter_chain: 2,
+                    emitter_address,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(last.sequence, Some(6));
+
+        let gaps: MissingSequencesResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::MissingSequences {
+                    emitter_chain: 2,
+                    emitter_address,
+                    start: 0,
+                    end: 130,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(gaps.missing[..3], [2, 4, 5]);
+        assert_eq!(gaps.missing.len(), 130 - 4);
+    }
+
+    #[test]
+    fn missing_sequences_at_the_top_of_the_range() {
+        let mut deps = setup(instantiate_msg(4));
+        let emitter = message_with_sequence(u64::MAX - 5);
+        register_emitter(&mut deps, &emitter, false);
+        submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &emitter)).unwrap();
+
+        let gaps = query_missing_sequences(
+            deps.as_ref(),
+            2,
+            &emitter.emitter_address,
+            u64::MAX - 10,
+            u64::MAX,
+        )
+        .unwrap();
+        assert_eq!(gaps.missing.len(), 9);
+        assert!(!gaps.missing.contains(&(u64::MAX - 5)));
+        assert_eq!(gaps.missing.last(), Some(&(u64::MAX - 1)));
+    }
+
+    #[test]
+    fn strict_ordering_rejects_gaps() {
+        let mut deps = setup(instantiate_msg(4));
+        register_emitter(&mut deps, &message_with_sequence(0), true);
+
+        submit_vaa(
+            &mut deps,
+            sign_vaa(0, &[0, 1, 2], &message_with_sequence(5)),
+        )
+        .unwrap();
+        let err = submit_vaa(
+            &mut deps,
+            sign_vaa(0, &[0, 1, 2], &message_with_sequence(7)),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::SequenceOutOfOrder {}));
+        submit_vaa(
+            &mut deps,
+            sign_vaa(0, &[0, 1, 2], &message_with_sequence(6)),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn unregistered_emitter_is_rejected() {
+        let mut deps = setup(instantiate_msg(4));
+        let err = submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &Body::message(2, 1))).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidVAAAction {}));
+    }
+
+    #[test]
+    fn vaa_status_by_message_id() {
+        let mut deps = setup(instantiate_msg(4));
+        let body = message_with_sequence(7);
+        register_emitter(&mut deps, &body, false);
+        submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &body)).unwrap();
+
+        let status = |sequence: u64| -> VAAStatusResponse {
+            let msg = QueryMsg::VAAStatus {
+                emitter_chain: body.emitter_chain,
+                emitter_address: ExternalAddress(body.emitter_address),
+                sequence,
+            };
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+        };
+
+        let consumed = status(7).consumed.unwrap();
+        let digest = Keccak256::digest(Keccak256::digest(body.serialize()));
+        assert_eq!(consumed.digest.as_slice(), digest.as_slice());
+        assert_eq!(consumed.block_height, mock_env().block.height);
+        assert_eq!(consumed.block_time, mock_env().block.time.seconds());
+        assert_eq!(status(8).consumed, None);
+    }
+
+    fn query_receipt(deps: &MockDeps, body: &Body) -> Option<VAAReceipt> {
+        let digest = Keccak256::digest(Keccak256::digest(body.serialize()));
+        let msg = QueryMsg::VAAReceipt {
+            digest: VaaDigest(digest.into()),
+        };
+        let res: VAAReceiptResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        res.receipt
+    }
+
+    #[test]
+    fn receipts_record_consumption() {
+        let mut deps = setup(instantiate_msg(4));
+        let body = message_with_sequence(3);
+        let receiver = register_emitter(&mut deps, &body, false);
+        submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &body)).unwrap();
+
+        let receipt = query_receipt(&deps, &body).unwrap();
+        assert_eq!(receipt.submitter, Some(deps.api.addr_make("submitter")));
+        assert_eq!(receipt.emitter_chain, body.emitter_chain);
+        assert_eq!(receipt.emitter_address.as_slice(), body.emitter_address);
+        assert_eq!(receipt.sequence, 3);
+        assert_eq!(receipt.action, VAAAction::Dispatched { receiver });
+        assert_eq!(receipt.block_height, mock_env().block.height);
+
+        let gov = Body::governance(129, &[0u8; 18]);
+        submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &gov)).unwrap();
+        let receipt = query_receipt(&deps, &gov).unwrap();
+        assert_eq!(receipt.action, VAAAction::Governance { action: 129 });
+    }
+
+    #[test]
+    fn migrate_legacy_archive_to_receipts() {
+        let mut deps = setup(instantiate_msg(4));
+        let body = Body::message(2, 1);
+        let digest = Keccak256::digest(Keccak256::digest(body.serialize()));
+        LEGACY_CONSUMED_VAA_ARCHIVE
+            .save(deps.as_mut().storage, digest.as_slice(), &true)
+            .unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        assert_eq!(res.attributes[1].value, "1");
+        let receipt = query_receipt(&deps, &body).unwrap();
+        assert_eq!(receipt.action, VAAAction::Legacy {});
+        assert_eq!(receipt.submitter, None);
+
+        // Running the migration again leaves receipts untouched
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        assert_eq!(res.attributes[1].value, "0");
+
+        // Entries that are neither legacy flags nor receipts fail the migration
+        let key = LEGACY_CONSUMED_VAA_ARCHIVE.key([7u8; 32].as_slice());
+        deps.storage.set(&key, b"{\"corrupt\":1}");
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+    }
+
+    #[test]
+    fn migrate_archive_in_batches() {
+        let mut deps = setup(instantiate_msg(4));
+        for i in 1..=5u8 {
+            LEGACY_CONSUMED_VAA_ARCHIVE
+                .save(deps.as_mut().storage, [i; 32].as_slice(), &true)
+                .unwrap();
+        }
+
+        let mut msg = MigrateMsg {
+            start_after: None,
+            limit: Some(2),
+        };
+        let mut batches = vec![];
+        loop {
+            let res = migrate(deps.as_mut(), mock_env(), msg.clone()).unwrap();
+            let attribute = |key: &str| {
+                res.attributes
+                    .iter()
+                    .find(|attr| attr.key == key)
+                    .unwrap()
+                    .value
+                    .clone()
+            };
+            batches.push(attribute("migrated_receipts"));
+            if attribute("complete") == "true" {
+                break;
+            }
+            let last_digest = hex::decode(attribute("last_digest")).unwrap();
+            msg.start_after = Some(VaaDigest::try_from(last_digest.as_slice()).unwrap());
+        }
+        assert_eq!(batches, ["2", "2", "1"]);
+        for i in 1..=5u8 {
+            let receipt = CONSUMED_VAA_ARCHIVE
+                .load(&deps.storage, [i; 32].as_slice())
+                .unwrap();
+            assert_eq!(receipt.action, VAAAction::Legacy {});
+        }
+    }
+
+    #[test]
+    fn migrate_rejects_other_contracts_and_downgrades() {
+        let mut deps = setup(instantiate_msg(4));
+
+        set_contract_version(deps.as_mut().storage, "crates.io:other", CONTRACT_VERSION).unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+        assert!(matches!(err, ContractError::WrongContract {}));
+
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+        assert!(matches!(err, ContractError::CannotDowngrade {}));
+
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.0.1").unwrap();
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        let version = get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.version, CONTRACT_VERSION);
+    }
+
+    #[test]
+    fn vaa_ref_reads_raw_buffer() {
+        let body = message_with_sequence(42);
+        let data = sign_vaa(0, &[0, 2], &body);
+        let vaa = VaaRef::parse(&data).unwrap();
+
+        assert_eq!(vaa.guardian_set_index(), 0);
+        assert_eq!(
+            vaa.signatures().map(|sig| sig.index()).collect::<Vec<_>>(),
+            [0, 2]
+        );
+        assert_eq!(vaa.body(), body.serialize());
+        assert_eq!(vaa.emitter_address(), body.emitter_address);
+        assert_eq!(vaa.sequence(), 42);
+        assert_eq!(vaa.payload(), body.payload);
+
+        let parsed = ParsedVAA::from(vaa);
+        assert_eq!(parsed, ParsedVAA::deserialize(&data).unwrap());
+        assert_eq!(parsed.hash, vaa.digest());
+
+        // Truncated bodies and headers are rejected
+        assert!(VaaRef::parse(&data[..data.len() - body.payload.len() - 1]).is_err());
+        assert!(VaaRef::parse(&data[..3]).is_err());
+    }
+
+    #[test]
+    fn addresses_and_digests_are_hex_in_json() {
+        let address = ExternalAddress(GOV_ADDRESS);
+        let json = to_json_binary(&address).unwrap();
+        assert_eq!(
+            json.as_slice(),
+            format!("\"{}\"", hex::encode(GOV_ADDRESS)).as_bytes()
+        );
+        assert_eq!(from_json::<ExternalAddress>(&json).unwrap(), address);
+        assert!(from_json::<VaaDigest>(br#""0x0004""#).is_err());
+
+        // The governance address of the instantiate message is checked when it is decoded
+        let msg = to_json_string(&instantiate_msg(4)).unwrap();
+        assert!(msg.contains(&hex::encode(GOV_ADDRESS)));
+        let msg = msg.replace(&hex::encode(GOV_ADDRESS), "04");
+        assert!(from_json::<InstantiateMsg>(msg).is_err());
+    }
+
+    #[test]
+    fn migrate_legacy_config() {
+        let mut deps = setup(instantiate_msg(4));
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        LEGACY_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &LegacyConfigInfo {
+                    guardian_set_index: config.guardian_set_index,
+                    guardian_set_expirity: config.guardian_set_expirity,
+                    gov_chain: GOV_CHAIN,
+                    gov_address: GOV_ADDRESS.to_vec(),
+                    chain_id: config.chain_id,
+                },
+            )
+            .unwrap();
+        GOVERNANCE_EMITTERS.clear(deps.as_mut().storage);
+
+        // The governance emitter moves out of the config into the governance emitter set
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        assert_eq!(CONFIG.load(deps.as_ref().storage).unwrap(), config);
+        assert!(LEGACY_CONFIG.load(deps.as_ref().storage).is_err());
+        assert_eq!(
+            governance_emitters(&deps),
+            vec![GovernanceEmitter {
+                emitter_chain: GOV_CHAIN,
+                emitter_address: ExternalAddress::from(GOV_ADDRESS),
+            }]
+        );
+        submit_governance(&mut deps, 0, 0, 133, &[1]).unwrap();
+    }
+
+    #[test]
+    fn verify_vaa_response_derived_fields() {
+        let deps = setup(instantiate_msg(4));
+        let body = message_with_sequence(9);
+        let res = verify_query(&deps, sign_vaa(0, &[0, 1, 3], &body), false).unwrap();
+
+        let digest = Keccak256::digest(Keccak256::digest(body.serialize()));
+        assert_eq!(res.hash, VaaDigest(digest.into()));
+        assert_eq!(
+            res.message_id,
+            format!("2/{}/9", hex::encode(body.emitter_address))
+        );
+        assert_eq!(res.signer_indexes, [0, 1, 3]);
+        assert_eq!(
+            res.signers,
+            [0, 1, 3].map(|index| GuardianSigner {
+                index,
+                address: guardian_address(&guardian_key(index)),
+            })
+        );
+        assert!(res.guardian_set_active);
+        assert_eq!(res.guardian_set_expiration_time, 0);
+    }
+
+    #[test]
+    fn verify_vaa_response_schema() {
+        let schemas = QueryMsg::response_schemas().unwrap();
+        assert_eq!(schemas["verify_v_a_a"], schema_for!(VerifyVAAResponse));
+
+        let schema = schema_for!(VerifyVAAResponse);
+        let required = &schema.schema.object.as_ref().unwrap().required;
+        assert_eq!(
+            required.iter().map(String::as_str).collect::<Vec<_>>(),
+            [
+                "consistency_level",
+                "emitter_address",
+                "emitter_chain",
+                "guardian_set_active",
+                "guardian_set_expiration_time",
+                "guardian_set_index",
+                "hash",
+                "len_signers",
+                "message_id",
+                "nonce",
+                "payload",
+                "sequence",
+                "signer_indexes",
+                "signers",
+                "timestamp",
+                "version",
+            ]
+        );
+        for name in ["ExternalAddress", "VaaDigest"] {
+            let Schema::Object(definition) = &schema.definitions[name] else {
+                panic!("{name} is not an object schema");
+            };
+            assert_eq!(definition.instance_type, Some(InstanceType::String.into()));
+        }
+    }
+
+    #[test]
+    fn verify_vaa_with_stricter_threshold() {
+        let deps = setup(instantiate_msg(19));
+        let query_threshold = |signers: &[u8], min_signatures: u32| {
+            let msg = QueryMsg::VerifyVAAWithThreshold {
+                vaa: sign_vaa(0, signers, &Body::message(2, 1)).into(),
+                block_time: 0,
+                min_signatures,
+            };
+            query(deps.as_ref(), mock_env(), msg)
+        };
+
+        let quorum = (0..13).collect::<Vec<_>>();
+        let fifteen = (0..15).collect::<Vec<_>>();
+
+        query_threshold(&quorum, 0).unwrap();
+        let err = query_threshold(&quorum, 15).unwrap_err();
+        assert!(err.to_string().contains("ThresholdNotMet"));
+        query_threshold(&fifteen, 15).unwrap();
+
+        // The base quorum is never relaxed by a lower threshold
+        let err = query_threshold(&quorum[..12], 1).unwrap_err();
+        assert!(err.to_string().contains("No quorum"));
+    }
+
+    #[test]
+    fn configurable_quorum_rule() {
+        let mut msg = instantiate_msg(5);
+        msg.quorum_rule = Some(QuorumRule::Threshold { signatures: 2 });
+        let deps = setup(msg);
+        verify_query(&deps, sign_vaa(0, &[1, 4], &Body::message(2, 1)), false).unwrap();
+        assert!(verify_query(&deps, sign_vaa(0, &[1], &Body::message(2, 1)), false).is_err());
+
+        let mut msg = instantiate_msg(6);
+        msg.quorum_rule = Some(QuorumRule::Fraction {
+            numerator: 1,
+            denominator: 2,
+        });
+        let deps = setup(msg);
+        let res: QuorumResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Quorum {}).unwrap()).unwrap();
+        assert_eq!(res.quorum, 4);
+        verify_query(
+            &deps,
+            sign_vaa(0, &[0, 1, 2, 3], &Body::message(2, 1)),
+            false,
+        )
+        .unwrap();
+        assert!(verify_query(&deps, sign_vaa(0, &[0, 1, 2], &Body::message(2, 1)), false).is_err());
+
+        // Large fractions do not overflow on 32 bit targets
+        let mut msg = instantiate_msg(6);
+        msg.quorum_rule = Some(QuorumRule::Fraction {
+            numerator: u32::MAX - 1,
+            denominator: u32::MAX,
+        });
+        let deps = setup(msg);
+        let res: QuorumResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Quorum {}).unwrap()).unwrap();
+        assert_eq!(res.quorum, 6);
+    }
+
+    #[test]
+    fn invalid_quorum_rules_are_rejected() {
+        let instantiate_with = |num_guardians: u8, quorum_rule: QuorumRule, test_mode: bool| {
+            let mut msg = instantiate_msg(num_guardians);
+            msg.quorum_rule = Some(quorum_rule);
+            msg.test_mode = test_mode;
+            let mut deps = mock_dependencies();
+            let info = message_info(&deps.api.addr_make("creator"), &[]);
+            instantiate(deps.as_mut(), mock_env(), info, msg)
+        };
+
+        let unreachable = QuorumRule::Threshold { signatures: 4 };
+        let err = instantiate_with(3, unreachable, false).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidQuorumRule {}));
+        let whole = QuorumRule::Fraction {
+            numerator: 3,
+            denominator: 3,
+        };
+        let err = instantiate_with(3, whole, false).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidQuorumRule {}));
+
+        let err = instantiate_with(0, QuorumRule::Wormhole {}, false).unwrap_err();
+        assert!(matches!(err, ContractError::EmptyGuardianSet {}));
+    }
+
+    #[test]
+    fn guardian_set_upgrade_validated_against_quorum_rule() {
+        let mut msg = instantiate_msg(4);
+        msg.quorum_rule = Some(QuorumRule::Threshold { signatures: 3 });
+        let mut deps = setup(msg);
+
+        let gov = Body::governance(2, &guardian_set_upgrade(1, 2));
+        let err = submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &gov)).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidQuorumRule {}));
+
+        let gov = Body::governance(2, &guardian_set_upgrade(1, 0));
+        let err = submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &gov)).unwrap_err();
+        assert!(matches!(err, ContractError::EmptyGuardianSet {}));
+
+        let gov = Body::governance(2, &guardian_set_upgrade(1, 3));
+        submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &gov)).unwrap();
+    }
+
+    /// Replaces signature `i` of the VAA with its high S twin, which recovers the same key
+    fn malleate_signature(vaa: &mut [u8], i: usize) {
+        let pos = ParsedVAA::HEADER_LEN + i * ParsedVAA::SIGNATURE_LEN;
+        let data = pos + ParsedVAA::SIG_DATA_POS..pos + ParsedVAA::SIG_RECOVERY_POS;
+        let signature = Signature::try_from(&vaa[data.clone()]).unwrap();
+        let (r, s) = signature.split_scalars();
+        let malleated = Signature::from_scalars(r, -s).unwrap();
+        vaa[data].copy_from_slice(&malleated.to_bytes());
+        vaa[pos + ParsedVAA::SIG_RECOVERY_POS] ^= 1;
+    }
+
+    #[test]
+    fn malleated_signatures_rejected() {
+        let mut deps = setup(instantiate_msg(4));
+        let body = message_with_sequence(1);
+        register_emitter(&mut deps, &body, false);
+
+        let original = sign_vaa(0, &[0, 1, 2], &body);
+        let mut malleated = original.clone();
+        malleate_signature(&mut malleated, 1);
+        assert_ne!(original, malleated);
+
+        // The malleated signature is the high S twin of the original one
+        let signature_at = |vaa: &[u8]| {
+            let vaa = VaaRef::parse(vaa).unwrap();
+            Signature::try_from(vaa.signatures().nth(1).unwrap().signature()).unwrap()
+        };
+        assert_eq!(
+            signature_at(&malleated).normalize_s(),
+            Some(signature_at(&original))
+        );
+
+        let err = submit_vaa(&mut deps, malleated.clone()).unwrap_err();
+        assert!(err.to_string().contains("MalleableSignature"));
+
+        // Replays are caught by the digest of the signed body, not the signature encoding
+        submit_vaa(&mut deps, original.clone()).unwrap();
+        let err = submit_vaa(&mut deps, original.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::VaaAlreadyExecuted {}));
+
+        // Both encodings map to the same receipt
+        let receipt = |vaa: &[u8]| {
+            let msg = QueryMsg::VAAReceipt {
+                digest: VaaRef::parse(vaa).unwrap().digest(),
+            };
+            let res: VAAReceiptResponse =
+                from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            res.receipt
+        };
+        assert!(receipt(&original).is_some());
+        assert_eq!(receipt(&malleated), receipt(&original));
+    }
+
+    #[test]
+    fn non_canonical_recovery_id_rejected() {
+        let deps = setup(instantiate_msg(4));
+        let mut vaa = sign_vaa(0, &[0, 1, 2], &Body::message(2, 1));
+        vaa[ParsedVAA::HEADER_LEN + ParsedVAA::SIG_RECOVERY_POS] += 2;
+
+        let err = verify_query(&deps, vaa, false).unwrap_err();
+        assert!(err.to_string().contains("InvalidRecoveryId"));
+    }
+
+    /// Overwrites the r and s of signature `i` with zeroes, which no key recovery accepts
+    fn corrupt_signature(vaa: &mut [u8], i: usize) {
+        let pos = ParsedVAA::HEADER_LEN + i * ParsedVAA::SIGNATURE_LEN;
+        vaa[pos + ParsedVAA::SIG_DATA_POS..pos + ParsedVAA::SIG_RECOVERY_POS].fill(0);
+    }
+
+    /// Counts the key recoveries of the contract. The host charges a fixed gas cost per
+    /// recovery, the bulk of the verification gas.
+    #[derive(Default)]
+    struct RecoveryCountingApi {
+        api: MockApi,
+        recoveries: Cell<usize>,
+    }
+
+    impl Api for RecoveryCountingApi {
+        fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+            self.api.addr_validate(human)
+        }
+
+        fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+            self.api.addr_canonicalize(human)
+        }
+
+        fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+            self.api.addr_humanize(canonical)
+        }
+
+        fn secp256k1_verify(
+            &self,
+            message_hash: &[u8],
+            signature: &[u8],
+            public_key: &[u8],
+        ) -> Result<bool, VerificationError> {
+            self.api
+                .secp256k1_verify(message_hash, signature, public_key)
+        }
+
+        fn secp256k1_recover_pubkey(
+            &self,
+            message_hash: &[u8],
+            signature: &[u8],
+            recovery_param: u8,
+        ) -> Result<Vec<u8>, RecoverPubkeyError> {
+            self.recoveries.set(self.recoveries.get() + 1);
+            self.api
+                .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+        }
+
+        fn ed25519_verify(
+            &self,
+            message: &[u8],
+            signature: &[u8],
+            public_key: &[u8],
+        ) -> Result<bool, VerificationError> {
+            self.api.ed25519_verify(message, signature, public_key)
+        }
+
+        fn ed25519_batch_verify(
+            &self,
+            messages: &[&[u8]],
+            signatures: &[&[u8]],
+            public_keys: &[&[u8]],
+        ) -> Result<bool, VerificationError> {
+            self.api
+                .ed25519_batch_verify(messages, signatures, public_keys)
+        }
+
+        fn debug(&self, message: &str) {
+            self.api.debug(message)
+        }
+    }
+
+    /// Verifies `vaa` and returns the result with the number of key recoveries it took
+    fn verify_counting_recoveries(
+        deps: &MockDeps,
+        vaa: &[u8],
+    ) -> (StdResult<VerifyVAAResponse>, usize) {
+        let api = RecoveryCountingApi::default();
+        let counting = Deps {
+            storage: &deps.storage,
+            api: &api,
+            querier: QuerierWrapper::new(&deps.querier),
+        };
+        let res = query_verify_vaa(counting, vaa, mock_env().block.time.seconds(), false);
+        (res, api.recoveries.get())
+    }
+
+    #[test]
+    fn structural_errors_rejected_before_recovery() {
+        let deps = setup(instantiate_msg(4));
+        let body = Body::message(2, 1);
+
+        // A valid VAA pays for one recovery per signature
+        let valid = sign_vaa(0, &[0, 1, 2], &body);
+        let (res, recoveries) = verify_counting_recoveries(&deps, &valid);
+        res.unwrap();
+        assert_eq!(recoveries, 3);
+
+        // A junk first signature fails before any recovery
+        let mut junk = valid;
+        corrupt_signature(&mut junk, 0);
+        let (res, recoveries) = verify_counting_recoveries(&deps, &junk);
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot decode signature"));
+        assert_eq!(recoveries, 0);
+
+        // Structural errors on the last signature are found before recovering the first ones
+        let last = ParsedVAA::HEADER_LEN + 2 * ParsedVAA::SIGNATURE_LEN;
+        let mut valid_first = sign_vaa(0, &[0, 1, 2], &body);
+        valid_first[last] = 4;
+        let (res, recoveries) = verify_counting_recoveries(&deps, &valid_first);
+        assert!(res.unwrap_err().to_string().contains("Too many signatures"));
+        assert_eq!(recoveries, 0);
+
+        valid_first[last] = 1;
+        let (res, recoveries) = verify_counting_recoveries(&deps, &valid_first);
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Wrong guardian index order"));
+        assert_eq!(recoveries, 0);
+
+        valid_first[last] = 2;
+        valid_first[last + ParsedVAA::SIGNATURE_LEN - 1] += 2;
+        let (res, recoveries) = verify_counting_recoveries(&deps, &valid_first);
+        assert!(res.unwrap_err().to_string().contains("InvalidRecoveryId"));
+        assert_eq!(recoveries, 0);
+    }
+
+    #[test]
+    fn signature_count_checked_against_guardian_set_size() {
+        let deps = setup(instantiate_msg(2));
+        let mut vaa = sign_vaa(0, &[0, 1], &Body::message(2, 1));
+        corrupt_signature(&mut vaa, 0);
+
+        // Claim a third signature by splicing a copy of the last one in front of the body
+        let signatures_end = ParsedVAA::HEADER_LEN + 2 * ParsedVAA::SIGNATURE_LEN;
+        let last = vaa[signatures_end - ParsedVAA::SIGNATURE_LEN..signatures_end].to_vec();
+        vaa.splice(signatures_end..signatures_end, last);
+        vaa[ParsedVAA::LEN_SIGNER_POS] = 3;
+
+        let err = verify_query(&deps, vaa, false).unwrap_err();
+        assert!(err.to_string().contains("Too many signatures"));
+    }
+
+    fn verify_signatures_query(
+        deps: &MockDeps,
+        digest: VaaDigest,
+        signatures: Vec<GuardianSignature>,
+    ) -> StdResult<VerifySignaturesResponse> {
+        let msg = QueryMsg::VerifySignatures {
+            digest,
+            signatures,
+            guardian_set_index: 0,
+            block_time: 0,
+        };
+        query(deps.as_ref(), mock_env(), msg).and_then(from_json)
+    }
+
+    #[test]
+    fn verify_signatures_over_arbitrary_digest() {
+        let deps = setup(instantiate_msg(4));
+        let digest = VaaDigest::from(<[u8; 32]>::from(Keccak256::digest(b"observation")));
+        let sign = |signers: &[u8]| -> Vec<GuardianSignature> {
+            signers
+                .iter()
+                .map(|&index| GuardianSignature {
+                    index,
+                    signature: sign_digest(index, digest.as_slice()).into(),
+                })
+                .collect()
+        };
+
+        let res = verify_signatures_query(&deps, digest, sign(&[0, 2, 3])).unwrap();
+        let indexes: Vec<u8> = res.signers.iter().map(|s| s.index).collect();
+        assert_eq!(indexes, vec![0, 2, 3]);
+
+        let err = verify_signatures_query(&deps, digest, sign(&[0, 2])).unwrap_err();
+        assert!(err.to_string().contains("No quorum"));
+
+        // Signatures over another digest recover other keys
+        let other = VaaDigest::from([7u8; 32]);
+        let err = verify_signatures_query(&deps, other, sign(&[0, 2, 3])).unwrap_err();
+        assert!(err.to_string().contains("Guardian signature error"));
+
+        let mut truncated = sign(&[0, 2, 3]);
+        truncated[1].signature = truncated[1].signature[..64].to_vec().into();
+        let err = verify_signatures_query(&deps, digest, truncated).unwrap_err();
+        assert!(err.to_string().contains("Invalid signature length"));
+    }
+
+    #[test]
+    fn verify_signatures_matches_vaa_verification() {
+        let deps = setup(instantiate_msg(4));
+        let vaa = sign_vaa(0, &[1, 2, 3], &Body::message(2, 1));
+        let vaa_ref = VaaRef::parse(&vaa).unwrap();
+
+        let signatures = vaa_ref
+            .signatures()
+            .map(|sig| GuardianSignature {
+                index: sig.index(),
+                signature: [sig.signature(), &[sig.recovery_id()]].concat().into(),
+            })
+            .collect();
+        let res = verify_signatures_query(&deps, vaa_ref.digest(), signatures).unwrap();
+        assert_eq!(
+            res.signers,
+            verify_query(&deps, vaa, false).unwrap().signers
+        );
+    }
+
+    #[test]
+    fn verify_query_response_signatures() {
+        let deps = setup(instantiate_msg(4));
+        let data = query_response(
+            1,
+            &[PerChainQuery::eth_call(
+                2,
+                "latest",
+                &[([0x11; 20], b"", b"\x01")],
+            )],
+        );
+        let query_verify = |digest: VaaDigest, signers: &[u8]| {
+            let msg = QueryMsg::VerifyQueryResponse {
+                response: data.clone().into(),
+                signatures: signers
+                    .iter()
+                    .map(|&index| GuardianSignature {
+                        index,
+                        signature: sign_digest(index, digest.as_slice()).into(),
+                    })
+                    .collect(),
+            };
+            query(deps.as_ref(), mock_env(), msg).and_then(from_json)
+        };
+
+        let digest = query_response_digest(&data);
+        let res: VerifyQueryResponseResponse = query_verify(digest, &[0, 1, 3]).unwrap();
+        assert_eq!(res.response, QueryResponse::deserialize(&data).unwrap());
+        assert_eq!(res.signers.len(), 3);
+
+        let err = query_verify(digest, &[0, 1]).unwrap_err();
+        assert!(err.to_string().contains("No quorum"));
+
+        // Signing the bare response hash without the query response prefix is not accepted
+        let unprefixed = VaaDigest::from(<[u8; 32]>::from(Keccak256::digest(&data)));
+        let err = query_verify(unprefixed, &[0, 1, 3]).unwrap_err();
+        assert!(err.to_string().contains("Guardian signature error"));
+    }
+
+    #[test]
+    fn governance_sets_guardian_set_expiry() {
+        let mut deps = setup(instantiate_msg(4));
+        let now = mock_env().block.time.seconds();
+
+        let payload = 10u64.to_be_bytes();
+        submit_governance(&mut deps, 0, 0, 131, &payload).unwrap();
+        assert_eq!(
+            CONFIG.load(&deps.storage).unwrap().guardian_set_expirity,
+            10
+        );
+
+        submit_governance(&mut deps, 0, 1, 2, &guardian_set_upgrade(1, 4)).unwrap();
+        let old = GUARDIAN_SET.load(&deps.storage, 0).unwrap();
+        assert_eq!(old.expiration_time, now + 10);
+
+        let err = submit_governance(&mut deps, 1, 2, 131, &payload[1..]).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidVAA {}));
+    }
+
+    #[test]
+    fn governance_expires_and_extends_old_guardian_set() {
+        let mut deps = setup(instantiate_msg(4));
+        let now = mock_env().block.time.seconds();
+        submit_governance(&mut deps, 0, 0, 2, &guardian_set_upgrade(1, 4)).unwrap();
+
+        let old_set_vaa = sign_vaa(0, &[0, 1, 2], &Body::message(2, 1));
+        verify_query(&deps, old_set_vaa.clone(), false).unwrap();
+
+        let set_expiration = |index: u32, expiration_time: u64| {
+            [
+                index.to_be_bytes().as_slice(),
+                &expiration_time.to_be_bytes(),
+            ]
+            .concat()
+        };
+
+        // Cut the old set validity immediately
+        submit_governance(&mut deps, 1, 1, 132, &set_expiration(0, 0)).unwrap();
+        let old = GUARDIAN_SET.load(&deps.storage, 0).unwrap();
+        assert_eq!(old.expiration_time, now - 1);
+        let err = verify_query(&deps, old_set_vaa.clone(), false).unwrap_err();
+        assert!(err.to_string().contains("Guardian set expired"));
+
+        // And extend it again
+        submit_governance(&mut deps, 1, 2, 132, &set_expiration(0, now + 100)).unwrap();
+        verify_query(&deps, old_set_vaa, false).unwrap();
+
+        let err = submit_governance(&mut deps, 1, 3, 132, &set_expiration(1, 0)).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::CannotExpireCurrentGuardianSet {}
+        ));
+
+        let err = submit_governance(&mut deps, 1, 4, 132, &set_expiration(5, 0)).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidGuardianSetIndex {}));
+    }
+
+    #[test]
+    fn pause_blocks_messages_but_not_governance() {
+        let mut deps = setup(instantiate_msg(4));
+        register_emitter(&mut deps, &message_with_sequence(0), false);
+        let paused = |deps: &MockDeps| {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus {});
+            from_json::<PauseStatusResponse>(res.unwrap())
+                .unwrap()
+                .paused
+        };
+        assert!(!paused(&deps));
+
+        submit_governance(&mut deps, 0, 1, 133, &[1]).unwrap();
+        assert!(paused(&deps));
+
+        let message = sign_vaa(0, &[0, 1, 2], &message_with_sequence(0));
+        let err = submit_vaa(&mut deps, message.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Paused {}));
+
+        // Governance keeps working while paused, including the unpause itself
+        submit_governance(&mut deps, 0, 2, 2, &guardian_set_upgrade(1, 4)).unwrap();
+        submit_governance(&mut deps, 1, 3, 133, &[0]).unwrap();
+        assert!(!paused(&deps));
+        submit_vaa(&mut deps, message).unwrap();
+
+        let err = submit_governance(&mut deps, 1, 4, 133, &[2]).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidVAA {}));
+    }
+
+    fn execute_as(
+        deps: &mut MockDeps,
+        sender: &str,
+        msg: ExecuteMsg,
+    ) -> Result<Response, ContractError> {
+        let info = message_info(&deps.api.addr_make(sender), &[]);
+        execute(deps.as_mut(), mock_env(), info, msg)
+    }
+
+    fn query_ownership_state(deps: &MockDeps) -> Ownership {
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Ownership {}).unwrap()).unwrap()
+    }
+
+    fn query_allowed_submitters_state(deps: &MockDeps) -> Option<Vec<Addr>> {
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::AllowedSubmitters {}).unwrap();
+        from_json::<AllowedSubmittersResponse>(res)
+            .unwrap()
+            .submitters
+    }
+
+    #[test]
+    fn admin_actions_are_limited_and_logged() {
+        let mut msg = instantiate_msg(4);
+        let admin = MockApi::default().addr_make("admin");
+        msg.admin = Some(admin.to_string());
+        let mut deps = setup(msg);
+        let now = mock_env().block.time.seconds();
+        register_emitter(&mut deps, &message_with_sequence(0), false);
+        submit_governance(&mut deps, 0, 1, 2, &guardian_set_upgrade(1, 4)).unwrap();
+
+        let err = execute_as(&mut deps, "intruder", ExecuteMsg::Pause {}).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        // Restricting submitters only applies to non-governance VAAs
+        let relayer = deps.api.addr_make("relayer");
+        let msg = ExecuteMsg::RestrictSubmitters {
+            submitters: Some(vec![relayer.to_string()]),
+        };
+        let res = execute_as(&mut deps, "admin", msg).unwrap();
+        assert!(res
+            .attributes
+            .contains(&attr("action", "admin_restrict_submitters")));
+        assert!(res.attributes.contains(&attr("admin", admin.as_str())));
+        let message = sign_vaa(0, &[0, 1, 2], &message_with_sequence(0));
+        let err = submit_vaa(&mut deps, message.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let msg = ExecuteMsg::SubmitVAA {
+            vaa: message.into(),
+        };
+        execute_as(&mut deps, "relayer", msg).unwrap();
+
+        // Old guardian set expirations can only be brought forward
+        let shorten =
+            |guardian_set_index, expiration_time| ExecuteMsg::ShortenGuardianSetExpiration {
+                guardian_set_index,
+                expiration_time,
+            };
+        let res = execute_as(&mut deps, "admin", shorten(0, now + 10)).unwrap();
+        assert!(res.attributes.contains(&attr("admin", admin.as_str())));
+        let err = execute_as(&mut deps, "admin", shorten(0, now + 20)).unwrap_err();
+        assert!(matches!(err, ContractError::ExpirationNotShortened {}));
+        let err = execute_as(&mut deps, "admin", shorten(1, 0)).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::CannotExpireCurrentGuardianSet {}
+        ));
+
+        let res = execute_as(&mut deps, "admin", ExecuteMsg::Pause {}).unwrap();
+        assert!(res.attributes.contains(&attr("action", "admin_pause")));
+        assert!(PAUSED.load(&deps.storage).unwrap());
+
+        // Revoking the admin also lifts its submitter restriction
+        submit_governance(&mut deps, 1, 2, 134, &[]).unwrap();
+        assert_eq!(query_ownership_state(&deps), Ownership::default());
+        assert_eq!(query_allowed_submitters_state(&deps), None);
+        let err = execute_as(&mut deps, "admin", ExecuteMsg::Pause {}).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+    }
+
+    fn update_ownership(
+        deps: &mut MockDeps,
+        env: Env,
+        sender: &str,
+        action: OwnershipAction,
+    ) -> Result<Response, ContractError> {
+        let info = message_info(&deps.api.addr_make(sender), &[]);
+        execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::UpdateOwnership(action),
+        )
+    }
+
+    #[test]
+    fn admin_transfer_takes_two_steps() {
+        let mut msg = instantiate_msg(4);
+        msg.admin = Some(MockApi::default().addr_make("admin").to_string());
+        let mut deps = setup(msg);
+        let env = mock_env();
+        let successor = deps.api.addr_make("successor");
+
+        let transfer = |expiry| OwnershipAction::TransferOwnership {
+            new_owner: successor.to_string(),
+            expiry,
+        };
+        let err =
+            update_ownership(&mut deps, env.clone(), "successor", transfer(None)).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        update_ownership(&mut deps, env.clone(), "admin", transfer(None)).unwrap();
+
+        // The current admin keeps its role until the transfer is accepted
+        let state = query_ownership_state(&deps);
+        assert_eq!(state.owner, Some(deps.api.addr_make("admin")));
+        assert_eq!(state.pending_owner, Some(successor.clone()));
+
+        let accept = || OwnershipAction::AcceptOwnership;
+        let err = update_ownership(&mut deps, env.clone(), "intruder", accept()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        update_ownership(&mut deps, env.clone(), "successor", accept()).unwrap();
+
+        let state = query_ownership_state(&deps);
+        assert_eq!(state.owner, Some(successor.clone()));
+        assert_eq!(state.pending_owner, None);
+        let err = execute_as(&mut deps, "admin", ExecuteMsg::Pause {}).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute_as(&mut deps, "successor", ExecuteMsg::Pause {}).unwrap();
+
+        // Messages are wire compatible with cw-ownable
+        let msg: ExecuteMsg = from_json(br#"{"update_ownership":"accept_ownership"}"#).unwrap();
+        assert_eq!(msg, ExecuteMsg::UpdateOwnership(accept()));
+    }
+
+    #[test]
+    fn admin_transfer_can_expire_be_cancelled_and_renounced() {
+        let mut msg = instantiate_msg(4);
+        msg.admin = Some(MockApi::default().addr_make("admin").to_string());
+        let mut deps = setup(msg);
+        let env = mock_env();
+        let transfer = |expiry| OwnershipAction::TransferOwnership {
+            new_owner: MockApi::default().addr_make("successor").to_string(),
+            expiry,
+        };
+
+        // Transfers expiring in the past are refused, live ones cannot be accepted once expired
+        let expired = Some(Expiration::AtHeight(env.block.height));
+        let err = update_ownership(&mut deps, env.clone(), "admin", transfer(expired)).unwrap_err();
+        assert!(matches!(err, ContractError::OwnershipTransferExpired {}));
+        let expiry = Some(Expiration::AtHeight(env.block.height + 10));
+        update_ownership(&mut deps, env.clone(), "admin", transfer(expiry)).unwrap();
+        assert_eq!(query_ownership_state(&deps).pending_expiry, expiry);
+        let mut later = env.clone();
+        later.block.height += 10;
+        let accept = || OwnershipAction::AcceptOwnership;
+        let err = update_ownership(&mut deps, later, "successor", accept()).unwrap_err();
+        assert!(matches!(err, ContractError::OwnershipTransferExpired {}));
+
+        let cancel = || OwnershipAction::CancelOwnershipTransfer;
+        let err = update_ownership(&mut deps, env.clone(), "successor", cancel()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        update_ownership(&mut deps, env.clone(), "admin", cancel()).unwrap();
+        let state = query_ownership_state(&deps);
+        assert_eq!(state.pending_owner, None);
+        assert_eq!(state.pending_expiry, None);
+        let err = update_ownership(&mut deps, env.clone(), "successor", accept()).unwrap_err();
+        assert!(matches!(err, ContractError::OwnershipTransferNotFound {}));
+        let err = update_ownership(&mut deps, env.clone(), "admin", cancel()).unwrap_err();
+        assert!(matches!(err, ContractError::OwnershipTransferNotFound {}));
+
+        // Renouncing also lifts the submitter restriction, which nobody could lift afterwards
+        let msg = ExecuteMsg::RestrictSubmitters {
+            submitters: Some(vec![]),
+        };
+        execute_as(&mut deps, "admin", msg).unwrap();
+        update_ownership(&mut deps, env.clone(), "admin", transfer(None)).unwrap();
+        let renounce = || OwnershipAction::RenounceOwnership;
+        let err = update_ownership(&mut deps, env.clone(), "successor", renounce()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let res = update_ownership(&mut deps, env.clone(), "admin", renounce()).unwrap();
+        assert!(res
+            .attributes
+            .contains(&attr("action", "renounce_ownership")));
+        assert_eq!(query_ownership_state(&deps), Ownership::default());
+        assert_eq!(query_allowed_submitters_state(&deps), None);
+        let err = update_ownership(&mut deps, env, "successor", accept()).unwrap_err();
+        assert!(matches!(err, ContractError::OwnershipTransferNotFound {}));
+    }
+
+    fn guardian_set(num_guardians: u8) -> GuardianSetInfo {
+        instantiate_msg(num_guardians).initial_guardian_set
+    }
+
+    #[test]
+    fn sudo_recovers_guardian_sets() {
+        let mut deps = setup(instantiate_msg(4));
+        let now = mock_env().block.time.seconds();
+        let set_guardian_set = |index, guardian_set| SudoMsg::SetGuardianSet {
+            index,
+            guardian_set,
+        };
+
+        let err = sudo(
+            deps.as_mut(),
+            mock_env(),
+            set_guardian_set(2, guardian_set(4)),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::GuardianSetIndexIncreaseError {}
+        ));
+        let err = sudo(
+            deps.as_mut(),
+            mock_env(),
+            set_guardian_set(1, guardian_set(0)),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::EmptyGuardianSet {}));
+
+        let mut next = guardian_set(4);
+        next.expiration_time = now;
+        let res = sudo(deps.as_mut(), mock_env(), set_guardian_set(1, next)).unwrap();
+        assert!(res.attributes.contains(&attr("origin", "sudo")));
+        assert_eq!(CONFIG.load(&deps.storage).unwrap().guardian_set_index, 1);
+        assert_eq!(
+            GUARDIAN_SET.load(&deps.storage, 1).unwrap().expiration_time,
+            0
+        );
+        assert_eq!(
+            GUARDIAN_SET.load(&deps.storage, 0).unwrap().expiration_time,
+            now + 86400
+        );
+
+        let old_set_vaa = sign_vaa(0, &[0, 1, 2], &Body::message(2, 1));
+        verify_query(&deps, old_set_vaa.clone(), false).unwrap();
+        let expire = |index| SudoMsg::ExpireGuardianSet { index };
+        sudo(deps.as_mut(), mock_env(), expire(0)).unwrap();
+        let err = verify_query(&deps, old_set_vaa, false).unwrap_err();
+        assert!(err.to_string().contains("Guardian set expired"));
+
+        let err = sudo(deps.as_mut(), mock_env(), expire(1)).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::CannotExpireCurrentGuardianSet {}
+        ));
+
+        // Recoveries are recorded without a governance VAA
+        let entries = governance_history(&deps, None, None);
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].action, entries[1].action), (2, 132));
+        assert_eq!(entries[0].name, "guardian_set_change");
+        assert!(entries
+            .iter()
+            .all(|entry| entry.origin == GovernanceOrigin::Sudo && entry.digest.is_none()));
+        assert_eq!(
+            entries[1].params,
+            vec![
+                attr("guardian_set_index", "0"),
+                attr("expiration_time", (now - 1).to_string()),
+            ]
+        );
+    }
+
+    fn set_governor_limit(
+        deps: &mut MockDeps,
+        sequence: u64,
+        max_vaas: u64,
+        max_notional: u128,
+        release_delay: u64,
+    ) {
+        let mut payload = 2u16.to_be_bytes().to_vec();
+        payload.extend_from_slice(&max_vaas.to_be_bytes());
+        payload.extend_from_slice(&max_notional.to_be_bytes());
+        payload.extend_from_slice(&release_delay.to_be_bytes());
+        submit_governance(deps, 0, sequence, 135, &payload).unwrap();
+    }
+
+    fn governor_usage(deps: &MockDeps, env: Env) -> GovernorUsageResponse {
+        let msg = QueryMsg::GovernorUsage { emitter_chain: 2 };
+        from_json(query(deps.as_ref(), env, msg).unwrap()).unwrap()
+    }
+
+    fn pending_vaas(deps: &MockDeps) -> Vec<PendingVAA> {
+        let msg = QueryMsg::PendingVAAs {
+            start_after: None,
+            limit: None,
+        };
+        let res: PendingVAAsResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        res.pending
+    }
+
+    const TOKEN: [u8; 32] = [0xa; 32];
+
+    /// USD `price` of one whole `token` of chain 2, with 8 decimals
+    fn set_token_price(deps: &mut MockDeps, sequence: u64, token: [u8; 32], price: u128) {
+        let mut payload = 2u16.to_be_bytes().to_vec();
+        payload.extend_from_slice(&token);
+        payload.extend_from_slice(&price.to_be_bytes());
+        submit_governance(deps, 0, sequence, 141, &payload).unwrap();
+    }
+
+    fn token_transfer(sequence: u64, tokens: u128) -> Body {
+        token_transfer_of(sequence, TOKEN, tokens)
+    }
+
+    /// Transfer of `tokens` whole tokens of `token`
+    fn token_transfer_of(sequence: u64, token: [u8; 32], tokens: u128) -> Body {
+        let mut body = message_with_sequence(sequence);
+        body.payload = vec![1u8];
+        body.payload.extend_from_slice(&[0u8; 16]);
+        body.payload
+            .extend_from_slice(&(tokens * 10u128.pow(8)).to_be_bytes());
+        body.payload.extend_from_slice(&token);
+        body.payload.extend_from_slice(&2u16.to_be_bytes());
+        body.payload.extend_from_slice(&[0u8; 66]);
+        body
+    }
+
+    #[test]
+    fn governor_queues_vaas_over_the_count_limit() {
+        let mut deps = setup(instantiate_msg(4));
+        let receiver = register_emitter(&mut deps, &message_with_sequence(0), false);
+        set_governor_limit(&mut deps, 1, 2, 0, 3600);
+
+        for sequence in 0..2 {
+            let vaa = sign_vaa(0, &[0, 1, 2], &message_with_sequence(sequence));
+            assert_eq!(submit_vaa(&mut deps, vaa).unwrap().messages.len(), 1);
+        }
+        let queued = message_with_sequence(2);
+        let res = submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &queued)).unwrap();
+        assert!(res.messages.is_empty());
+        assert!(res.attributes.contains(&attr("action", "queue_vaa")));
+        assert_eq!(
+            query_receipt(&deps, &queued).unwrap().action,
+            VAAAction::Queued {
+                receiver: receiver.clone()
+            }
+        );
+
+        let usage = governor_usage(&deps, mock_env());
+        assert_eq!(usage.vaas, 2);
+        let pending = pending_vaas(&deps);
+        assert_eq!(pending.len(), 1);
+        let digest = pending[0].vaa.hash;
+        assert_eq!(
+            pending[0].release_time,
+            mock_env().block.time.seconds() + 3600
+        );
+
+        let release = ExecuteMsg::ReleasePendingVAA { digest };
+        let err = execute_as(&mut deps, "anyone", release.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::ReleaseTimeNotReached {}));
+
+        // Anyone can release the VAA once the delay has passed
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3600);
+        let info = message_info(&deps.api.addr_make("anyone"), &[]);
+        let res = execute(deps.as_mut(), env.clone(), info, release.clone()).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            query_receipt(&deps, &queued).unwrap().action,
+            VAAAction::Dispatched { receiver }
+        );
+        assert!(pending_vaas(&deps).is_empty());
+        let err = execute_as(&mut deps, "anyone", release).unwrap_err();
+        assert!(matches!(err, ContractError::PendingVAANotFound {}));
+
+        // Usage leaves the window after 24h
+        env.block.time = mock_env().block.time.plus_seconds(GOVERNOR_WINDOW);
+        assert_eq!(governor_usage(&deps, env).vaas, 0);
+    }
+
+    #[test]
+    fn governor_queues_transfers_over_the_notional_limit() {
+        let mut deps = setup(instantiate_msg(4));
+        register_token_bridge(&mut deps, &message_with_sequence(0), false);
+        set_governor_limit(&mut deps, 1, 0, 1000, 86400);
+        let (cheap_token, unpriced_token) = ([0xb; 32], [0xc; 32]);
+        set_token_price(&mut deps, 3, TOKEN, 10u128.pow(8));
+        set_token_price(&mut deps, 4, cheap_token, 10u128.pow(5));
+        let price: TokenPriceResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::TokenPrice {
+                    token_chain: 2,
+                    token_address: ExternalAddress(cheap_token),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(price.price, Some(Uint128::new(10u128.pow(5))));
+
+        // Transfers are valued at the price of their token, 600 tokens at $0.001 are $0.6
+        let transfers = [
+            token_transfer(0, 600),
+            token_transfer_of(1, cheap_token, 600),
+            token_transfer_of(2, unpriced_token, 1_000_000),
+        ];
+        for transfer in &transfers {
+            let res = submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], transfer)).unwrap();
+            assert_eq!(res.messages.len(), 1);
+        }
+        let res = submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &token_transfer(3, 600))).unwrap();
+        assert!(res.messages.is_empty());
+
+        // Other payloads carry no value
+        let vaa = sign_vaa(0, &[0, 1, 2], &message_with_sequence(4));
+        assert_eq!(submit_vaa(&mut deps, vaa).unwrap().messages.len(), 1);
+
+        // Nor do transfer shaped payloads of emitters that are not token bridges
+        let mut other_app = token_transfer(0, 600);
+        other_app.emitter_address = [7u8; 32];
+        register_emitter(&mut deps, &other_app, false);
+        let vaa = sign_vaa(0, &[0, 1, 2], &other_app);
+        assert_eq!(submit_vaa(&mut deps, vaa).unwrap().messages.len(), 1);
+
+        let usage = governor_usage(&deps, mock_env());
+        assert_eq!(usage.vaas, 5);
+        assert_eq!(usage.notional, Uint128::new(600));
+
+        // Governance releases queued VAAs without waiting for the delay
+        let pending = pending_vaas(&deps);
+        assert_eq!(pending[0].notional, Uint128::new(600));
+        let digest = pending[0].vaa.hash;
+        let res = submit_governance(&mut deps, 0, 2, 136, digest.as_slice()).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert!(pending_vaas(&deps).is_empty());
+
+        // Removing a price stops valuing the token
+        set_token_price(&mut deps, 5, TOKEN, 0);
+        let vaa = sign_vaa(0, &[0, 1, 2], &token_transfer(5, 600));
+        assert_eq!(submit_vaa(&mut deps, vaa).unwrap().messages.len(), 1);
+    }
+
+    #[test]
+    fn governor_keeps_strict_emitters_in_order() {
+        let mut deps = setup(instantiate_msg(4));
+        register_token_bridge(&mut deps, &message_with_sequence(0), true);
+        set_governor_limit(&mut deps, 1, 0, 1000, 3600);
+        set_token_price(&mut deps, 3, TOKEN, 10u128.pow(8));
+
+        // A small transfer is held back behind a queued one instead of overtaking it
+        let large = token_transfer(0, 2000);
+        let small = token_transfer(1, 1);
+        for body in [&large, &small] {
+            let res = submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], body)).unwrap();
+            assert!(res.messages.is_empty());
+            assert!(res.attributes.contains(&attr("action", "queue_vaa")));
+        }
+        let pending = pending_vaas(&deps);
+        let digest = |sequence: u64| {
+            pending
+                .iter()
+                .find(|pending| pending.vaa.sequence == sequence)
+                .unwrap()
+                .vaa
+                .hash
+        };
+        let (large_digest, small_digest) = (digest(0), digest(1));
+        assert_eq!(governor_usage(&deps, mock_env()).vaas, 0);
+
+        // Neither governance nor anyone else can release it first
+        let err = submit_governance(&mut deps, 0, 2, 136, small_digest.as_slice()).unwrap_err();
+        assert!(matches!(err, ContractError::SequenceOutOfOrder {}));
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3600);
+        let release = |deps: &mut MockDeps, digest| {
+            let info = message_info(&deps.api.addr_make("anyone"), &[]);
+            let msg = ExecuteMsg::ReleasePendingVAA { digest };
+            execute(deps.as_mut(), env.clone(), info, msg)
+        };
+        let err = release(&mut deps, small_digest).unwrap_err();
+        assert!(matches!(err, ContractError::SequenceOutOfOrder {}));
+
+        assert_eq!(release(&mut deps, large_digest).unwrap().messages.len(), 1);
+        assert_eq!(release(&mut deps, small_digest).unwrap().messages.len(), 1);
+
+        // With nothing queued the emitter is only governed by the limits again
+        let next = sign_vaa(0, &[0, 1, 2], &token_transfer(2, 1));
+        assert_eq!(submit_vaa(&mut deps, next).unwrap().messages.len(), 1);
+    }
+
+    #[test]
+    fn timelocked_governance_actions_wait_for_the_delay() {
+        let mut msg = instantiate_msg(4);
+        msg.governance_delay = 3600;
+        let mut deps = setup(msg);
+        let emitter = message_with_sequence(0);
+        let emitter_address = ExternalAddress::from(emitter.emitter_address);
+        let receiver = register_emitter(&mut deps, &emitter, false);
+        let registration = |deps: &MockDeps| {
+            query_emitter_registration(deps.as_ref(), 2, emitter_address.as_slice())
+                .unwrap()
+                .registration
+        };
+        assert_eq!(registration(&deps), None);
+
+        let msg = QueryMsg::PendingGovernanceActions {
+            start_after: None,
+            limit: None,
+        };
+        let res: PendingGovernanceActionsResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.delay, 3600);
+        assert_eq!(res.actions.len(), 1);
+        assert_eq!(res.actions[0].action, 130);
+        let pending_digest = res.actions[0].digest;
+        assert_eq!(
+            res.actions[0].executable_at,
+            mock_env().block.time.seconds() + 3600
+        );
+
+        // Critical actions are never delayed
+        submit_governance(&mut deps, 0, 1, 133, &[1]).unwrap();
+        assert!(PAUSED.load(&deps.storage).unwrap());
+
+        // Malformed timelocked actions are rejected on submission
+        let err = submit_governance(&mut deps, 0, 2, 137, &[0]).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidVAA {}));
+
+        let execute_pending = ExecuteMsg::ExecutePending { id: 0 };
+        let err = execute_as(&mut deps, "anyone", execute_pending.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::TimelockNotExpired {}));
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3600);
+        let info = message_info(&deps.api.addr_make("anyone"), &[]);
+        let res = execute(deps.as_mut(), env, info, execute_pending.clone()).unwrap();
+        assert!(res.attributes.contains(&attr("action", "register_emitter")));
+        assert_eq!(registration(&deps).unwrap().receiver, receiver);
+
+        let err = execute_as(&mut deps, "anyone", execute_pending).unwrap_err();
+        assert!(matches!(err, ContractError::PendingActionNotFound {}));
+
+        // The history records the action when executed, under the digest of its VAA
+        let entries = governance_history(&deps, None, None);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].name, "register_emitter");
+        assert_eq!(entries[1].digest, Some(pending_digest));
+    }
+
+    #[test]
+    fn governance_cancels_pending_actions_that_cannot_execute() {
+        let mut msg = instantiate_msg(4);
+        msg.governance_delay = 3600;
+        let mut deps = setup(msg);
+
+        // Removing an emitter that was never added fails once the delay is over
+        let mut payload = 5u16.to_be_bytes().to_vec();
+        payload.extend_from_slice(&[9; 32]);
+        submit_governance(&mut deps, 0, 0, 139, &payload).unwrap();
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3600);
+        let info = message_info(&deps.api.addr_make("anyone"), &[]);
+        let err = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::ExecutePending { id: 0 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::GovernanceEmitterNotFound {}));
+
+        // Cancelling applies immediately
+        let res = submit_governance(&mut deps, 0, 1, 140, &0u64.to_be_bytes()).unwrap();
+        assert!(res
+            .attributes
+            .contains(&attr("action", "cancel_pending_governance")));
+        assert!(res.attributes.contains(&attr("governance_action", "139")));
+        assert!(!PENDING_GOVERNANCE.has(&deps.storage, 0));
+
+        let err = submit_governance(&mut deps, 0, 2, 140, &0u64.to_be_bytes()).unwrap_err();
+        assert!(matches!(err, ContractError::PendingActionNotFound {}));
+        let err = submit_governance(&mut deps, 0, 3, 140, &[0]).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidVAA {}));
+    }
+
+    fn governance_history(
+        deps: &MockDeps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Vec<GovernanceLogEntry> {
+        let msg = QueryMsg::GovernanceHistory { start_after, limit };
+        let res: GovernanceHistoryResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        res.entries
+    }
+
+    #[test]
+    fn governance_history_records_executed_actions() {
+        let mut deps = setup(instantiate_msg(4));
+        let env = mock_env();
+        register_emitter(&mut deps, &message_with_sequence(0), true);
+        submit_governance(&mut deps, 0, 1, 133, &[1]).unwrap();
+        submit_governance(&mut deps, 0, 2, 2, &guardian_set_upgrade(1, 4)).unwrap();
+
+        // Rejected governance VAAs leave no entry
+        submit_governance(&mut deps, 1, 3, 133, &[2]).unwrap_err();
+
+        let entries = governance_history(&deps, None, Some(2));
+        assert_eq!(entries.len(), 2);
+        let mut pause = Body::governance(133, &[1]);
+        pause.sequence = 1;
+        let pause_vaa = sign_vaa(0, &[0, 1, 2], &pause);
+        assert_eq!(
+            entries[1],
+            GovernanceLogEntry {
+                id: 1,
+                action: 133,
+                name: "set_paused".to_string(),
+                module: "Core".to_string(),
+                origin: GovernanceOrigin::Vaa,
+                digest: Some(VaaRef::parse(&pause_vaa).unwrap().digest()),
+                params: vec![attr("paused", "true")],
+                block_height: env.block.height,
+                block_time: env.block.time.seconds(),
+            }
+        );
+        assert_eq!(entries[0].name, "register_emitter");
+        assert!(entries[0].params.contains(&attr("strict_ordering", "true")));
+
+        let entries = governance_history(&deps, Some(1), None);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, 2);
+        assert_eq!(entries[0].name, "guardian_set_change");
+        let addresses = (0..4)
+            .map(|i| hex::encode(guardian_address(&guardian_key(i)).bytes.as_slice()))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert_eq!(
+            entries[0].params,
+            vec![
+                attr("new_guardian_set_index", "1"),
+                attr("addresses", addresses),
+                attr("quorum", "3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn governance_history_records_released_vaas() {
+        let mut deps = setup(instantiate_msg(4));
+        register_emitter(&mut deps, &message_with_sequence(0), false);
+        set_governor_limit(&mut deps, 1, 1, 0, 3600);
+        for sequence in 0..2 {
+            let vaa = sign_vaa(0, &[0, 1, 2], &message_with_sequence(sequence));
+            submit_vaa(&mut deps, vaa).unwrap();
+        }
+        let digest = pending_vaas(&deps)[0].vaa.hash;
+
+        // The release dispatches the VAA, the history still records the governance action
+        let res = submit_governance(&mut deps, 0, 2, 136, digest.as_slice()).unwrap();
+        assert!(res.attributes.contains(&attr("action", "receive_vaa")));
+
+        let entries = governance_history(&deps, Some(1), None);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, 136);
+        assert_eq!(entries[0].name, "release_pending_vaa");
+        assert_eq!(entries[0].params, vec![attr("digest", digest.to_string())]);
+    }
+
+    #[test]
+    fn submit_vaa_emits_typed_events() {
+        let mut deps = setup(instantiate_msg(4));
+        let env = mock_env();
+
+        let body = Body::governance(2, &guardian_set_upgrade(1, 3));
+        let vaa = sign_vaa(0, &[0, 1, 2], &body);
+        let res = submit_vaa(&mut deps, vaa.clone()).unwrap();
+
+        let addresses = (0..3)
+            .map(|i| hex::encode(guardian_address(&guardian_key(i)).bytes.as_slice()))
+            .collect::<Vec<_>>()
+            .join(",");
+        let expiration = (env.block.time.seconds() + 86400).to_string();
+        assert_eq!(
+            res.events,
+            vec![
+                Event::new("guardian_set_upgrade").add_attributes(vec![
+                    ("old_guardian_set_index", "0"),
+                    ("new_guardian_set_index", "1"),
+                    ("addresses", addresses.as_str()),
+                    ("quorum", "3"),
+                    ("old_guardian_set_expiration", expiration.as_str()),
+                ]),
+                Event::new("vaa_verified").add_attributes(vec![
+                    (
+                        "digest",
+                        VaaRef::parse(&vaa).unwrap().digest().to_string().as_str()
+                    ),
+                    ("emitter_chain", "1"),
+                    ("emitter_address", hex::encode(GOV_ADDRESS).as_str()),
+                    ("sequence", "0"),
+                ]),
+            ]
+        );
+    }
+
+    fn governance_emitters(deps: &MockDeps) -> Vec<GovernanceEmitter> {
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GovernanceEmitters {}).unwrap();
+        from_json::<GovernanceEmittersResponse>(res)
+            .unwrap()
+            .emitters
+    }
+
+    #[test]
+    fn governance_emitters_can_be_moved() {
+        let mut deps = setup(instantiate_msg(4));
+        let original = GovernanceEmitter {
+            emitter_chain: GOV_CHAIN,
+            emitter_address: ExternalAddress::from(GOV_ADDRESS),
+        };
+        assert_eq!(governance_emitters(&deps), vec![original.clone()]);
+
+        let new_address = [9u8; 32];
+        let emitter_payload = |chain: u16, address: &[u8; 32]| {
+            [chain.to_be_bytes().as_slice(), address.as_slice()].concat()
+        };
+        let err = submit_governance(
+            &mut deps,
+            0,
+            0,
+            139,
+            &emitter_payload(GOV_CHAIN, &GOV_ADDRESS),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::LastGovernanceEmitter {}));
+
+        submit_governance(&mut deps, 0, 1, 138, &emitter_payload(2, &new_address)).unwrap();
+        let moved = GovernanceEmitter {
+            emitter_chain: 2,
+            emitter_address: ExternalAddress::from(new_address),
+        };
+        assert_eq!(governance_emitters(&deps), vec![original, moved]);
+
+        // The new emitter governs the contract and retires the original one
+        let new_governance = |sequence: u64, action: u8, payload: &[u8]| {
+            let mut body = Body::governance(action, payload);
+            body.emitter_chain = 2;
+            body.emitter_address = new_address;
+            body.sequence = sequence;
+            sign_vaa(0, &[0, 1, 2], &body)
+        };
+        let removal = new_governance(0, 139, &emitter_payload(GOV_CHAIN, &GOV_ADDRESS));
+        submit_vaa(&mut deps, removal).unwrap();
+        assert_eq!(governance_emitters(&deps).len(), 1);
+
+        let err = submit_governance(&mut deps, 0, 2, 133, &[1]).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidVAAAction {}));
+        submit_vaa(&mut deps, new_governance(1, 133, &[1])).unwrap();
+        assert!(PAUSED.load(&deps.storage).unwrap());
+
+        let err = submit_vaa(
+            &mut deps,
+            new_governance(2, 139, &emitter_payload(GOV_CHAIN, &GOV_ADDRESS)),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::GovernanceEmitterNotFound {}));
+    }
+
+    #[test]
+    fn removing_a_governance_emitter_drops_its_pending_actions() {
+        let mut deps = setup(instantiate_msg(4));
+        let new_address = [9u8; 32];
+        let mut payload = 2u16.to_be_bytes().to_vec();
+        payload.extend_from_slice(&new_address);
+        submit_governance(&mut deps, 0, 0, 138, &payload).unwrap();
+        submit_governance(&mut deps, 0, 1, 137, &3600u64.to_be_bytes()).unwrap();
+
+        // The original emitter queues an action, the new one queues its removal
+        let mut consistency_level = 2u16.to_be_bytes().to_vec();
+        consistency_level.push(Finality::Finalized as u8);
+        submit_governance(&mut deps, 0, 2, 128, &consistency_level).unwrap();
+        let mut removal = GOV_CHAIN.to_be_bytes().to_vec();
+        removal.extend_from_slice(&GOV_ADDRESS);
+        let mut body = Body::governance(139, &removal);
+        body.emitter_chain = 2;
+        body.emitter_address = new_address;
+        submit_vaa(&mut deps, sign_vaa(0, &[0, 1, 2], &body)).unwrap();
+        let pending = PENDING_GOVERNANCE.load(&deps.storage, 0).unwrap();
+        assert_eq!(
+            pending.emitter,
+            GovernanceEmitter {
+                emitter_chain: GOV_CHAIN,
+                emitter_address: ExternalAddress::from(GOV_ADDRESS),
+            }
+        );
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3600);
+        let execute_pending = |deps: &mut MockDeps, id| {
+            let info = message_info(&deps.api.addr_make("anyone"), &[]);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info,
+                ExecuteMsg::ExecutePending { id },
+            )
+        };
+        let res = execute_pending(&mut deps, 1).unwrap();
+        assert!(res.attributes.contains(&attr("dropped_pending", "1")));
+
+        let err = execute_pending(&mut deps, 0).unwrap_err();
+        assert!(matches!(err, ContractError::PendingActionNotFound {}));
+        assert_eq!(
+            MIN_CONSISTENCY_LEVEL.may_load(&deps.storage, 2).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn migrate_keeps_rotated_governance_emitters() {
+        let mut deps = setup(instantiate_msg(4));
+        let rotated = GovernanceEmitter {
+            emitter_chain: 5,
+            emitter_address: ExternalAddress::from([9; 32]),
+        };
+        GOVERNANCE_EMITTERS
+            .save(
+                deps.as_mut().storage,
+                (rotated.emitter_chain, rotated.emitter_address.as_slice()),
+                &Empty {},
+            )
+            .unwrap();
+        GOVERNANCE_EMITTERS.remove(deps.as_mut().storage, (GOV_CHAIN, GOV_ADDRESS.as_slice()));
+
+        // The instantiation emitter is only seeded when migrating a 0.1.0 config
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        assert_eq!(governance_emitters(&deps), vec![rotated]);
+    }
+}
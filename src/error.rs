@@ -76,4 +76,121 @@ pub enum ContractError {
     /// Generic error when there is a problem with VAA structure
     #[error("InvalidVAA")]
     InvalidVAA {},
+
+    /// VAA was emitted with a weaker finality than required for its emitter chain
+    #[error("InsufficientConsistencyLevel")]
+    InsufficientConsistencyLevel {},
+
+    /// VAA timestamp is older than the maximum age allowed for its emitter chain
+    #[error("VaaTooOld")]
+    VaaTooOld {},
+
+    /// VAA timestamp is further in the future than allowed for its emitter chain
+    #[error("VaaTimestampInFuture")]
+    VaaTimestampInFuture {},
+
+    /// Emitter requires in-order delivery and the VAA is not the next sequence
+    #[error("SequenceOutOfOrder")]
+    SequenceOutOfOrder {},
+
+    /// Requested sequence range is empty or too large
+    #[error("InvalidSequenceRange")]
+    InvalidSequenceRange {},
+
+    /// Address or digest is not exactly 32 bytes long
+    #[error("InvalidBytes32Length")]
+    InvalidBytes32Length {},
+
+    /// VAA has fewer signatures than the threshold requested on top of the quorum
+    #[error("ThresholdNotMet")]
+    ThresholdNotMet {},
+
+    /// Quorum rule is malformed or cannot be reached by the guardian set
+    #[error("InvalidQuorumRule")]
+    InvalidQuorumRule {},
+
+    /// Guardian set has no guardians and test mode is disabled
+    #[error("EmptyGuardianSet")]
+    EmptyGuardianSet {},
+
+    /// Test mode requested on a build without the `testing` feature
+    #[error("TestModeUnavailable")]
+    TestModeUnavailable {},
+
+    /// Signature has a high S value, only the canonical low S form is accepted
+    #[error("MalleableSignature")]
+    MalleableSignature {},
+
+    /// Signature recovery id is not 0 or 1
+    #[error("InvalidRecoveryId")]
+    InvalidRecoveryId {},
+
+    /// Query response is truncated, has trailing bytes or does not match its request
+    #[error("InvalidQueryResponse")]
+    InvalidQueryResponse {},
+
+    /// Query response holds a per-chain query type this contract cannot decode
+    #[error("UnsupportedQueryType")]
+    UnsupportedQueryType {},
+
+    /// The current guardian set never expires, only old sets can be given an expiration
+    #[error("CannotExpireCurrentGuardianSet")]
+    CannotExpireCurrentGuardianSet {},
+
+    /// Contract is paused, only governance VAAs are processed
+    #[error("Paused")]
+    Paused {},
+
+    /// The admin can only bring a guardian set expiration forward
+    #[error("ExpirationNotShortened")]
+    ExpirationNotShortened {},
+
+    /// No admin transfer is pending
+    #[error("OwnershipTransferNotFound")]
+    OwnershipTransferNotFound {},
+
+    /// Admin transfer expired before it was accepted
+    #[error("OwnershipTransferExpired")]
+    OwnershipTransferExpired {},
+
+    /// No VAA with this digest is queued by the governor
+    #[error("PendingVAANotFound")]
+    PendingVAANotFound {},
+
+    /// Queued VAA can only be released by governance before its release time
+    #[error("ReleaseTimeNotReached")]
+    ReleaseTimeNotReached {},
+
+    /// No pending governance action with this id
+    #[error("PendingActionNotFound")]
+    PendingActionNotFound {},
+
+    /// Pending governance action is still within the governance delay
+    #[error("TimelockNotExpired")]
+    TimelockNotExpired {},
+
+    /// Emitter is not an accepted governance emitter
+    #[error("GovernanceEmitterNotFound")]
+    GovernanceEmitterNotFound {},
+
+    /// Migration target is a different contract
+    #[error("WrongContract")]
+    WrongContract {},
+
+    /// Migration would downgrade the contract
+    #[error("CannotDowngrade")]
+    CannotDowngrade {},
+
+    /// The last governance emitter cannot be removed
+    #[error("LastGovernanceEmitter")]
+    LastGovernanceEmitter {},
+}
+
+impl From<ContractError> for StdError {
+    fn from(err: ContractError) -> Self {
+        match err {
+            ContractError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
 }
@@ -0,0 +1,69 @@
+//! Typed custom events for indexers. wasmd prefixes custom event types with `wasm-`, so
+//! `guardian_set_upgrade` is indexed as `wasm-guardian_set_upgrade`.
+
+use cosmwasm_std::Event;
+
+use crate::state::{ExternalAddress, GuardianAddress, VaaDigest};
+
+/// Emitted when a new guardian set is installed, by governance VAA or sudo
+pub struct GuardianSetUpgradeEvent<'a> {
+    pub old_guardian_set_index: u32,
+    pub new_guardian_set_index: u32,
+    pub addresses: &'a [GuardianAddress],
+    pub quorum: usize,
+    /// Time after which the old guardian set no longer verifies VAAs
+    pub old_guardian_set_expiration: u64,
+}
+
+impl GuardianSetUpgradeEvent<'_> {
+    pub const TYPE: &'static str = "guardian_set_upgrade";
+}
+
+impl From<GuardianSetUpgradeEvent<'_>> for Event {
+    fn from(event: GuardianSetUpgradeEvent) -> Self {
+        let addresses = event
+            .addresses
+            .iter()
+            .map(|address| hex::encode(address.bytes.as_slice()))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        Event::new(GuardianSetUpgradeEvent::TYPE)
+            .add_attribute(
+                "old_guardian_set_index",
+                event.old_guardian_set_index.to_string(),
+            )
+            .add_attribute(
+                "new_guardian_set_index",
+                event.new_guardian_set_index.to_string(),
+            )
+            .add_attribute("addresses", addresses)
+            .add_attribute("quorum", event.quorum.to_string())
+            .add_attribute(
+                "old_guardian_set_expiration",
+                event.old_guardian_set_expiration.to_string(),
+            )
+    }
+}
+
+/// Emitted for every VAA consumed by SubmitVAA
+pub struct VaaVerifiedEvent {
+    pub digest: VaaDigest,
+    pub emitter_chain: u16,
+    pub emitter_address: ExternalAddress,
+    pub sequence: u64,
+}
+
+impl VaaVerifiedEvent {
+    pub const TYPE: &'static str = "vaa_verified";
+}
+
+impl From<VaaVerifiedEvent> for Event {
+    fn from(event: VaaVerifiedEvent) -> Self {
+        Event::new(VaaVerifiedEvent::TYPE)
+            .add_attribute("digest", event.digest.to_string())
+            .add_attribute("emitter_chain", event.emitter_chain.to_string())
+            .add_attribute("emitter_address", event.emitter_address.to_string())
+            .add_attribute("sequence", event.sequence.to_string())
+    }
+}
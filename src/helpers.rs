@@ -34,3 +34,14 @@ pub fn keys_equal(a: &VerifyingKey, b: &GuardianAddress) -> bool {
     }
     true
 }
+
+/// Whether an uncompressed SEC1 public key, as returned by `Api::secp256k1_recover_pubkey`,
+/// belongs to the guardian address
+pub fn pubkey_matches(pubkey: &[u8], address: &GuardianAddress) -> bool {
+    match pubkey.split_first() {
+        Some((0x04, point)) if point.len() == 64 => {
+            Keccak256::digest(point)[12..] == *address.bytes.as_slice()
+        }
+        _ => false,
+    }
+}
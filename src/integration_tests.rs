@@ -0,0 +1,130 @@
+use cosmwasm_std::{Addr, Empty};
+use cw_multi_test::error::AnyResult;
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, VerifyVAAResponse};
+use crate::testing::{guardian_set_upgrade, instantiate_msg, sign_vaa, Body};
+use crate::ContractError;
+
+fn contract_wormhole() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn instantiate_wormhole(app: &mut App, msg: InstantiateMsg) -> AnyResult<Addr> {
+    let code_id = app.store_code(contract_wormhole());
+    let owner = app.api().addr_make("owner");
+    app.instantiate_contract(code_id, owner, &msg, &[], "wormhole", None)
+}
+
+fn submit_vaa(app: &mut App, wormhole: &Addr, vaa: Vec<u8>) -> AnyResult<AppResponse> {
+    let sender = app.api().addr_make("relayer");
+    let msg = ExecuteMsg::SubmitVAA { vaa: vaa.into() };
+    app.execute_contract(sender, wormhole.clone(), &msg, &[])
+}
+
+fn contract_error(err: cw_multi_test::error::AnyError) -> ContractError {
+    err.downcast::<ContractError>().unwrap()
+}
+
+#[test]
+fn empty_guardian_set_rejected_at_instantiation() {
+    let mut app = App::default();
+    let err = instantiate_wormhole(&mut app, instantiate_msg(0)).unwrap_err();
+    assert!(matches!(
+        contract_error(err),
+        ContractError::EmptyGuardianSet {}
+    ));
+}
+
+#[cfg(not(feature = "testing"))]
+#[test]
+fn test_mode_rejected_in_default_build() {
+    let mut app = App::default();
+    let mut msg = instantiate_msg(0);
+    msg.test_mode = true;
+    let err = instantiate_wormhole(&mut app, msg).unwrap_err();
+    assert!(matches!(
+        contract_error(err),
+        ContractError::TestModeUnavailable {}
+    ));
+}
+
+#[test]
+fn unsigned_vaas_rejected() {
+    let mut app = App::default();
+    let wormhole = instantiate_wormhole(&mut app, instantiate_msg(1)).unwrap();
+
+    let unsigned = sign_vaa(0, &[], &Body::message(2, 1));
+    let res: Result<VerifyVAAResponse, _> = app.wrap().query_wasm_smart(
+        &wormhole,
+        &QueryMsg::VerifyVAA {
+            vaa: unsigned.clone().into(),
+            block_time: app.block_info().time.seconds(),
+            strict: None,
+        },
+    );
+    assert!(res.unwrap_err().to_string().contains("No quorum"));
+
+    let unsigned_gov = sign_vaa(0, &[], &Body::governance(2, &guardian_set_upgrade(1, 0)));
+    let err = submit_vaa(&mut app, &wormhole, unsigned_gov).unwrap_err();
+    assert!(err.root_cause().to_string().contains("No quorum"));
+}
+
+#[test]
+fn upgrade_to_empty_guardian_set_rejected() {
+    let mut app = App::default();
+    let wormhole = instantiate_wormhole(&mut app, instantiate_msg(1)).unwrap();
+
+    let gov = sign_vaa(0, &[0], &Body::governance(2, &guardian_set_upgrade(1, 0)));
+    let err = submit_vaa(&mut app, &wormhole, gov).unwrap_err();
+    assert!(matches!(
+        contract_error(err),
+        ContractError::EmptyGuardianSet {}
+    ));
+}
+
+#[test]
+fn guardian_set_upgrade_event_is_indexed_as_wasm_event() {
+    let mut app = App::default();
+    let wormhole = instantiate_wormhole(&mut app, instantiate_msg(1)).unwrap();
+
+    let gov = sign_vaa(0, &[0], &Body::governance(2, &guardian_set_upgrade(1, 1)));
+    let res = submit_vaa(&mut app, &wormhole, gov).unwrap();
+
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-guardian_set_upgrade")
+        .unwrap();
+    assert!(event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "new_guardian_set_index" && attr.value == "1"));
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-vaa_verified"));
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_mode_accepts_unsigned_vaas() {
+    let mut app = App::default();
+    let mut msg = instantiate_msg(0);
+    msg.test_mode = true;
+    let wormhole = instantiate_wormhole(&mut app, msg).unwrap();
+
+    let unsigned = sign_vaa(0, &[], &Body::message(2, 1));
+    let _: VerifyVAAResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &wormhole,
+            &QueryMsg::VerifyVAA {
+                vaa: unsigned.into(),
+                block_time: app.block_info().time.seconds(),
+                strict: None,
+            },
+        )
+        .unwrap();
+}
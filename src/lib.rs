@@ -1,8 +1,15 @@
 pub mod byte_utils;
 pub mod contract;
 mod error;
+pub mod events;
 pub mod helpers;
 pub mod msg;
+pub mod query_response;
 pub mod state;
 
+#[cfg(test)]
+mod integration_tests;
+#[cfg(test)]
+mod testing;
+
 pub use crate::error::ContractError;
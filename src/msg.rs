@@ -1,25 +1,143 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Binary;
+use cosmwasm_std::{Addr, Binary, Uint128};
+use cw_utils::Expiration;
 
-use crate::state::{GuardianAddress, GuardianSetInfo};
+use crate::query_response::QueryResponse;
+use crate::state::{
+    ConsumedVAA, EmitterRegistration, ExternalAddress, Finality, FreshnessPolicy,
+    GovernanceEmitter, GovernanceLogEntry, GovernorLimit, GuardianAddress, GuardianSetInfo,
+    GuardianSignature, GuardianSigner, Ownership, ParsedVAA, PendingGovernanceAction, PendingVAA,
+    QuorumRule, VAAReceipt, VaaDigest,
+};
 
 type HumanAddr = String;
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub gov_chain: u16,
-    pub gov_address: Binary,
+    pub gov_address: ExternalAddress,
 
     /// Guardian set to initialise the contract with.
     pub initial_guardian_set: GuardianSetInfo,
     pub guardian_set_expirity: u64,
 
     pub chain_id: u16,
+
+    /// Minimum finality required for VAAs of the given emitter chains.
+    #[serde(default)]
+    pub consistency_levels: Vec<ConsistencyLevelPolicy>,
+
+    /// Maximum age of VAAs from any emitter chain, unless overridden per chain.
+    pub freshness_policy: Option<FreshnessPolicy>,
+    #[serde(default)]
+    pub chain_freshness_policies: Vec<ChainFreshnessPolicy>,
+
+    /// Quorum rule of the guardian sets, the Wormhole rule if not set.
+    pub quorum_rule: Option<QuorumRule>,
+    /// Accept empty guardian sets with a quorum of zero. Requires a build with the `testing`
+    /// feature and is only meant for test networks.
+    #[serde(default)]
+    pub test_mode: bool,
+
+    /// Optional local admin, only able to pause, shorten old guardian set expirations and
+    /// restrict submitters. Guardian governance can revoke it.
+    pub admin: Option<HumanAddr>,
+
+    /// Delay in seconds of non-critical governance actions, applied immediately if 0.
+    #[serde(default)]
+    pub governance_delay: u64,
+}
+
+#[cw_serde]
+pub struct ConsistencyLevelPolicy {
+    pub emitter_chain: u16,
+    pub min_finality: Finality,
+}
+
+#[cw_serde]
+pub struct ChainFreshnessPolicy {
+    pub emitter_chain: u16,
+    pub policy: FreshnessPolicy,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    SubmitVAA { vaa: Binary },
+    SubmitVAA {
+        vaa: Binary,
+    },
+
+    /// Transfers, accepts, cancels or renounces the admin role, messages follow cw-ownable
+    UpdateOwnership(OwnershipAction),
+    /// Admin only, pauses non-governance VAAs. Unpausing requires a governance VAA.
+    Pause {},
+    /// Admin only, brings the expiration of an old guardian set forward, 0 expires it
+    /// immediately
+    ShortenGuardianSetExpiration {
+        guardian_set_index: u32,
+        expiration_time: u64,
+    },
+    /// Applies a timelocked governance action once the governance delay has passed
+    ExecutePending {
+        id: u64,
+    },
+    /// Delivers a VAA queued by the governor once its release time has passed
+    ReleasePendingVAA {
+        digest: VaaDigest,
+    },
+    /// Admin only, restricts who may submit non-governance VAAs, `None` lifts the restriction
+    RestrictSubmitters {
+        submitters: Option<Vec<HumanAddr>>,
+    },
+}
+
+/// Two-step transfer of the local admin role, compatible with cw-ownable's `Action`
+#[cw_serde]
+pub enum OwnershipAction {
+    /// Admin only, proposes a new admin who takes over once accepting, before `expiry` if set.
+    /// Replaces any pending transfer.
+    TransferOwnership {
+        new_owner: HumanAddr,
+        expiry: Option<Expiration>,
+    },
+    /// Accepts a pending transfer, sent by the proposed admin
+    AcceptOwnership,
+    /// Admin only, gives up the admin role for good and lifts the submitter restriction
+    RenounceOwnership,
+    /// Admin only, drops the pending transfer. Not part of cw-ownable.
+    CancelOwnershipTransfer,
+}
+
+/// Migrates the consumed VAA archive in batches. Run the migration again from the returned
+/// `last_digest` until `complete` is true.
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrateMsg {
+    /// Digest after which to continue the archive migration
+    #[serde(default)]
+    pub start_after: Option<VaaDigest>,
+    /// Maximum number of archive entries visited, at most 1000
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// Recovery messages of the chain governance module, subject to the same rules as guardian
+/// governance
+#[cw_serde]
+pub enum SudoMsg {
+    /// Installs guardian set `index`, which must follow the current one. The expiration time of
+    /// `guardian_set` is ignored, the current set never expires.
+    SetGuardianSet {
+        index: u32,
+        guardian_set: GuardianSetInfo,
+    },
+    /// Expires the old guardian set `index` immediately
+    ExpireGuardianSet { index: u32 },
+}
+
+/// Message delivered to the receiver registered for the emitter of a submitted VAA
+#[cw_serde]
+pub enum ReceiverExecuteMsg {
+    ReceiveVAA { vaa: ParsedVAA },
 }
 
 #[cw_serde]
@@ -28,8 +146,134 @@ pub enum QueryMsg {
     #[returns(GuardianSetInfoResponse)]
     GuardianSetInfo {},
 
+    /// Verifies the VAA signatures. In strict mode the VAA must additionally satisfy the
+    /// policies enforced on submission, i.e. the minimum consistency level and the
+    /// freshness policy evaluated at `block_time`.
     #[returns(VerifyVAAResponse)]
-    VerifyVAA { vaa: Binary, block_time: u64 },
+    VerifyVAA {
+        vaa: Binary,
+        block_time: u64,
+        strict: Option<bool>,
+    },
+
+    /// Verifies the VAA and additionally requires at least `min_signatures` valid guardian
+    /// signatures. The Wormhole quorum still applies if it is higher.
+    #[returns(VerifyVAAResponse)]
+    VerifyVAAWithThreshold {
+        vaa: Binary,
+        block_time: u64,
+        min_signatures: u32,
+    },
+
+    /// Verifies guardian signatures over an arbitrary 32 byte digest, for guardian-signed
+    /// artifacts other than VAAs. The digest is the hash the guardians signed, for a VAA that is
+    /// the double keccak256 of its body.
+    #[returns(VerifySignaturesResponse)]
+    VerifySignatures {
+        digest: VaaDigest,
+        signatures: Vec<GuardianSignature>,
+        guardian_set_index: u32,
+        block_time: u64,
+    },
+
+    /// Verifies a Wormhole Queries response signed by the current guardian set and returns its
+    /// decoded per-chain results
+    #[returns(VerifyQueryResponseResponse)]
+    VerifyQueryResponse {
+        response: Binary,
+        signatures: Vec<GuardianSignature>,
+    },
+
+    #[returns(QuorumResponse)]
+    Quorum {},
+
+    #[returns(ConsistencyLevelResponse)]
+    ConsistencyLevel { emitter_chain: u16 },
+
+    /// Freshness policy in effect for the emitter chain
+    #[returns(FreshnessPolicyResponse)]
+    FreshnessPolicy { emitter_chain: u16 },
+
+    #[returns(EmitterRegistrationResponse)]
+    EmitterRegistration {
+        emitter_chain: u16,
+        emitter_address: ExternalAddress,
+    },
+
+    /// Highest sequence consumed from the emitter
+    #[returns(LastSequenceResponse)]
+    LastSequence {
+        emitter_chain: u16,
+        emitter_address: ExternalAddress,
+    },
+
+    /// Sequences in `start..end` that have not been consumed from the emitter
+    #[returns(MissingSequencesResponse)]
+    MissingSequences {
+        emitter_chain: u16,
+        emitter_address: ExternalAddress,
+        start: u64,
+        end: u64,
+    },
+
+    /// Consumption status of the VAA with message id `emitter_chain/emitter_address/sequence`
+    #[returns(VAAStatusResponse)]
+    VAAStatus {
+        emitter_chain: u16,
+        emitter_address: ExternalAddress,
+        sequence: u64,
+    },
+
+    /// Receipt of the consumed VAA with the given digest
+    #[returns(VAAReceiptResponse)]
+    VAAReceipt { digest: VaaDigest },
+
+    /// Governor limit of the emitter chain and its usage over the current window
+    #[returns(GovernorUsageResponse)]
+    GovernorUsage { emitter_chain: u16 },
+
+    /// USD price the governor values transfers of the token at
+    #[returns(TokenPriceResponse)]
+    TokenPrice {
+        token_chain: u16,
+        token_address: ExternalAddress,
+    },
+
+    /// VAAs queued by the governor, ordered by digest
+    #[returns(PendingVAAsResponse)]
+    PendingVAAs {
+        start_after: Option<VaaDigest>,
+        limit: Option<u32>,
+    },
+
+    /// Timelocked governance actions waiting to be executed, ordered by id
+    #[returns(PendingGovernanceActionsResponse)]
+    PendingGovernanceActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Executed governance actions in execution order
+    #[returns(GovernanceHistoryResponse)]
+    GovernanceHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Emitters whose VAAs are executed as governance
+    #[returns(GovernanceEmittersResponse)]
+    GovernanceEmitters {},
+
+    /// Local admin and its pending transfer, in the cw-ownable layout
+    #[returns(Ownership)]
+    Ownership {},
+
+    #[returns(AllowedSubmittersResponse)]
+    AllowedSubmitters {},
+
+    /// Whether the contract is paused, rejecting non-governance VAAs
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
 
     #[returns(GetAddressHexResponse)]
     QueryAddressHex { address: HumanAddr },
@@ -50,12 +294,145 @@ pub struct VerifyVAAResponse {
     pub len_signers: u8,
 
     pub emitter_chain: u16,
-    pub emitter_address: Vec<u8>,
+    pub emitter_address: ExternalAddress,
     pub sequence: u64,
     pub consistency_level: u8,
     pub payload: Vec<u8>,
 
-    pub hash: Vec<u8>,
+    pub hash: VaaDigest,
+    pub message_id: String,
+    pub signer_indexes: Vec<u8>,
+    /// Guardians whose signatures were verified, empty unless the VAA was verified
+    pub signers: Vec<GuardianSigner>,
+
+    /// Whether the signing guardian set is the current one. A replaced set keeps verifying VAAs
+    /// until its expiration time.
+    pub guardian_set_active: bool,
+    pub guardian_set_expiration_time: u64,
+}
+
+impl From<ParsedVAA> for VerifyVAAResponse {
+    fn from(vaa: ParsedVAA) -> Self {
+        VerifyVAAResponse {
+            message_id: vaa.message_id(),
+            version: vaa.version,
+            guardian_set_index: vaa.guardian_set_index,
+            timestamp: vaa.timestamp,
+            nonce: vaa.nonce,
+            len_signers: vaa.len_signers,
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            consistency_level: vaa.consistency_level,
+            payload: vaa.payload,
+            hash: vaa.hash,
+            signer_indexes: vaa.signer_indexes,
+            signers: vec![],
+            guardian_set_active: false,
+            guardian_set_expiration_time: 0,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct VerifySignaturesResponse {
+    pub signers: Vec<GuardianSigner>,
+}
+
+#[cw_serde]
+pub struct VerifyQueryResponseResponse {
+    pub response: QueryResponse,
+    pub signers: Vec<GuardianSigner>,
+}
+
+#[cw_serde]
+pub struct QuorumResponse {
+    pub rule: QuorumRule,
+    pub test_mode: bool,
+    pub quorum: u32, // Signatures required with the current guardian set
+}
+
+#[cw_serde]
+pub struct ConsistencyLevelResponse {
+    pub emitter_chain: u16,
+    pub min_finality: Option<Finality>, // None if any consistency level is accepted
+}
+
+#[cw_serde]
+pub struct FreshnessPolicyResponse {
+    pub emitter_chain: u16,
+    pub policy: Option<FreshnessPolicy>, // None if VAAs never go stale
+}
+
+#[cw_serde]
+pub struct EmitterRegistrationResponse {
+    pub registration: Option<EmitterRegistration>,
+}
+
+#[cw_serde]
+pub struct LastSequenceResponse {
+    pub sequence: Option<u64>, // None if nothing was consumed from the emitter yet
+}
+
+#[cw_serde]
+pub struct MissingSequencesResponse {
+    pub missing: Vec<u64>,
+}
+
+#[cw_serde]
+pub struct VAAStatusResponse {
+    pub consumed: Option<ConsumedVAA>, // None if the VAA was not consumed
+}
+
+#[cw_serde]
+pub struct VAAReceiptResponse {
+    pub receipt: Option<VAAReceipt>, // None if the VAA was not consumed
+}
+
+#[cw_serde]
+pub struct GovernorUsageResponse {
+    pub limit: Option<GovernorLimit>, // None if the emitter chain is not governed
+    pub vaas: u64,
+    /// USD value of the transfers delivered within the window
+    pub notional: Uint128,
+}
+
+#[cw_serde]
+pub struct TokenPriceResponse {
+    /// USD price of one whole token with 8 decimals, None if the token is not valued
+    pub price: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct PendingVAAsResponse {
+    pub pending: Vec<PendingVAA>,
+}
+
+#[cw_serde]
+pub struct PendingGovernanceActionsResponse {
+    pub delay: u64,
+    pub actions: Vec<PendingGovernanceAction>,
+}
+
+#[cw_serde]
+pub struct GovernanceHistoryResponse {
+    pub entries: Vec<GovernanceLogEntry>,
+}
+
+#[cw_serde]
+pub struct GovernanceEmittersResponse {
+    pub emitters: Vec<GovernanceEmitter>,
+}
+
+#[cw_serde]
+pub struct AllowedSubmittersResponse {
+    /// Only these addresses may submit non-governance VAAs, anyone if None
+    pub submitters: Option<Vec<Addr>>,
+}
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub paused: bool,
 }
 
 #[cw_serde]
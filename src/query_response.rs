@@ -0,0 +1,691 @@
+//! Wormhole Queries (CCQ) responses: a guardian-signed envelope holding the original query
+//! request and the per-chain results observed by the guardians.
+//!
+//! Layouts follow the guardian node serialization, all integers are big endian.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, StdResult};
+use sha3::{Digest, Keccak256};
+
+use crate::state::{ExternalAddress, VaaDigest};
+use crate::ContractError;
+
+/// Prefix of the digest guardians sign over a query response
+pub const QUERY_RESPONSE_PREFIX: &[u8] = b"query_response_0000000000000000000|";
+
+pub const QUERY_RESPONSE_VERSION: u8 = 1;
+
+pub const QUERY_TYPE_ETH_CALL: u8 = 1;
+pub const QUERY_TYPE_ETH_CALL_BY_TIMESTAMP: u8 = 2;
+pub const QUERY_TYPE_ETH_CALL_WITH_FINALITY: u8 = 3;
+pub const QUERY_TYPE_SOLANA_ACCOUNT: u8 = 4;
+pub const QUERY_TYPE_SOLANA_PDA: u8 = 5;
+
+/// Digest guardians sign: keccak256(prefix || keccak256(response))
+pub fn query_response_digest(response: &[u8]) -> VaaDigest {
+    let mut hasher = Keccak256::new();
+    hasher.update(QUERY_RESPONSE_PREFIX);
+    hasher.update(Keccak256::digest(response));
+    VaaDigest::from(<[u8; 32]>::from(hasher.finalize()))
+}
+
+/// Decoded query response, each request query is paired with the guardians' response
+#[cw_serde]
+pub struct QueryResponse {
+    pub version: u8,
+    /// Chain the request was made on, 0 for off-chain requests
+    pub request_chain_id: u16,
+    /// Requester signature for off-chain requests, otherwise the hash of the request VAA
+    pub request_id: Binary,
+    pub nonce: u32,
+    pub responses: Vec<PerChainQueryResponse>,
+}
+
+#[cw_serde]
+pub struct PerChainQueryResponse {
+    pub chain_id: u16,
+    pub result: QueryResult,
+}
+
+#[cw_serde]
+pub enum QueryResult {
+    EthCall(EthCallResult),
+    EthCallByTimestamp(EthCallByTimestampResult),
+    EthCallWithFinality(EthCallWithFinalityResult),
+    SolanaAccount(SolanaAccountResult),
+    SolanaPda(SolanaPdaResult),
+}
+
+/// Single `eth_call` of a batch with its result
+#[cw_serde]
+pub struct EthCallData {
+    /// 20 byte contract address
+    pub contract_address: Binary,
+    pub call_data: Binary,
+    pub result: Binary,
+}
+
+/// Block times are in microseconds, as reported by the guardians
+#[cw_serde]
+pub struct EthCallResult {
+    pub request_block_id: String,
+    pub block_number: u64,
+    pub block_hash: Binary,
+    pub block_time_us: u64,
+    pub results: Vec<EthCallData>,
+}
+
+#[cw_serde]
+pub struct EthCallByTimestampResult {
+    pub request_target_time_us: u64,
+    pub request_target_block_hint: String,
+    pub request_following_block_hint: String,
+    pub target_block_number: u64,
+    pub target_block_hash: Binary,
+    pub target_block_time_us: u64,
+    pub following_block_number: u64,
+    pub following_block_hash: Binary,
+    pub following_block_time_us: u64,
+    pub results: Vec<EthCallData>,
+}
+
+#[cw_serde]
+pub struct EthCallWithFinalityResult {
+    pub request_block_id: String,
+    pub request_finality: String,
+    pub block_number: u64,
+    pub block_hash: Binary,
+    pub block_time_us: u64,
+    pub results: Vec<EthCallData>,
+}
+
+#[cw_serde]
+pub struct SolanaAccountData {
+    pub account: ExternalAddress,
+    pub lamports: u64,
+    pub rent_epoch: u64,
+    pub executable: bool,
+    pub owner: ExternalAddress,
+    pub data: Binary,
+}
+
+#[cw_serde]
+pub struct SolanaAccountResult {
+    pub request_commitment: String,
+    pub request_min_context_slot: u64,
+    pub request_data_slice_offset: u64,
+    pub request_data_slice_length: u64,
+    pub slot_number: u64,
+    pub block_time_us: u64,
+    pub block_hash: Binary,
+    pub results: Vec<SolanaAccountData>,
+}
+
+#[cw_serde]
+pub struct SolanaPdaData {
+    pub program_address: ExternalAddress,
+    pub seeds: Vec<Binary>,
+    pub account: ExternalAddress,
+    pub bump: u8,
+    pub lamports: u64,
+    pub rent_epoch: u64,
+    pub executable: bool,
+    pub owner: ExternalAddress,
+    pub data: Binary,
+}
+
+#[cw_serde]
+pub struct SolanaPdaResult {
+    pub request_commitment: String,
+    pub request_min_context_slot: u64,
+    pub request_data_slice_offset: u64,
+    pub request_data_slice_length: u64,
+    pub slot_number: u64,
+    pub block_time_us: u64,
+    pub block_hash: Binary,
+    pub results: Vec<SolanaPdaData>,
+}
+
+impl QueryResponse {
+    /*
+    Response envelope:
+     0   uint8       version
+     1   uint16      request chain id
+     3   [65|32]byte request id, signature for off-chain requests (chain 0)
+         uint32      request length
+         []byte      request
+         uint8       number of per-chain responses
+         per-chain:  uint16 chain id, uint8 query type, uint32 length, []byte response
+
+    Request:
+     0   uint8       version
+     1   uint32      nonce
+     5   uint8       number of per-chain queries
+         per-chain:  uint16 chain id, uint8 query type, uint32 length, []byte query
+    */
+
+    pub fn deserialize(data: &[u8]) -> StdResult<Self> {
+        let mut reader = Reader::new(data);
+
+        let version = reader.u8()?;
+        if version != QUERY_RESPONSE_VERSION {
+            Err(ContractError::InvalidVersion {})?
+        }
+
+        let request_chain_id = reader.u16()?;
+        let request_id_len = if request_chain_id == 0 { 65 } else { 32 };
+        let request_id = reader.bytes(request_id_len)?.to_vec().into();
+
+        let mut request = Reader::new(reader.var_bytes()?);
+        if request.u8()? != version {
+            Err(ContractError::InvalidVersion {})?
+        }
+        let nonce = request.u32()?;
+
+        let len_queries = request.u8()?;
+        if reader.u8()? != len_queries {
+            Err(ContractError::InvalidQueryResponse {})?
+        }
+
+        let responses = (0..len_queries)
+            .map(|_| {
+                let chain_id = request.u16()?;
+                let query_type = request.u8()?;
+                if reader.u16()? != chain_id || reader.u8()? != query_type {
+                    Err(ContractError::InvalidQueryResponse {})?
+                }
+
+                let query = Reader::new(request.var_bytes()?);
+                let response = Reader::new(reader.var_bytes()?);
+                let result = QueryResult::deserialize(query_type, query, response)?;
+
+                Ok(PerChainQueryResponse { chain_id, result })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        request.finish()?;
+        reader.finish()?;
+
+        Ok(QueryResponse {
+            version,
+            request_chain_id,
+            request_id,
+            nonce,
+            responses,
+        })
+    }
+}
+
+impl QueryResult {
+    fn deserialize(query_type: u8, mut query: Reader, mut response: Reader) -> StdResult<Self> {
+        let result = match query_type {
+            QUERY_TYPE_ETH_CALL => {
+                let request_block_id = query.string()?;
+                let calls = query.eth_calls()?;
+
+                QueryResult::EthCall(EthCallResult {
+                    request_block_id,
+                    block_number: response.u64()?,
+                    block_hash: response.bytes32()?.to_vec().into(),
+                    block_time_us: response.u64()?,
+                    results: response.eth_call_results(calls)?,
+                })
+            }
+            QUERY_TYPE_ETH_CALL_BY_TIMESTAMP => {
+                let request_target_time_us = query.u64()?;
+                let request_target_block_hint = query.string()?;
+                let request_following_block_hint = query.string()?;
+                let calls = query.eth_calls()?;
+
+                QueryResult::EthCallByTimestamp(EthCallByTimestampResult {
+                    request_target_time_us,
+                    request_target_block_hint,
+                    request_following_block_hint,
+                    target_block_number: response.u64()?,
+                    target_block_hash: response.bytes32()?.to_vec().into(),
+                    target_block_time_us: response.u64()?,
+                    following_block_number: response.u64()?,
+                    following_block_hash: response.bytes32()?.to_vec().into(),
+                    following_block_time_us: response.u64()?,
+                    results: response.eth_call_results(calls)?,
+                })
+            }
+            QUERY_TYPE_ETH_CALL_WITH_FINALITY => {
+                let request_block_id = query.string()?;
+                let request_finality = query.string()?;
+                let calls = query.eth_calls()?;
+
+                QueryResult::EthCallWithFinality(EthCallWithFinalityResult {
+                    request_block_id,
+                    request_finality,
+                    block_number: response.u64()?,
+                    block_hash: response.bytes32()?.to_vec().into(),
+                    block_time_us: response.u64()?,
+                    results: response.eth_call_results(calls)?,
+                })
+            }
+            QUERY_TYPE_SOLANA_ACCOUNT => {
+                let request_commitment = query.string()?;
+                let request_min_context_slot = query.u64()?;
+                let request_data_slice_offset = query.u64()?;
+                let request_data_slice_length = query.u64()?;
+                let accounts = (0..query.u8()?)
+                    .map(|_| query.address())
+                    .collect::<StdResult<Vec<_>>>()?;
+
+                let slot_number = response.u64()?;
+                let block_time_us = response.u64()?;
+                let block_hash = response.bytes32()?.to_vec().into();
+                if response.u8()? as usize != accounts.len() {
+                    Err(ContractError::InvalidQueryResponse {})?
+                }
+                let results = accounts
+                    .into_iter()
+                    .map(|account| {
+                        Ok(SolanaAccountData {
+                            account,
+                            lamports: response.u64()?,
+                            rent_epoch: response.u64()?,
+                            executable: response.bool()?,
+                            owner: response.address()?,
+                            data: response.var_bytes()?.to_vec().into(),
+                        })
+                    })
+                    .collect::<StdResult<Vec<_>>>()?;
+
+                QueryResult::SolanaAccount(SolanaAccountResult {
+                    request_commitment,
+                    request_min_context_slot,
+                    request_data_slice_offset,
+                    request_data_slice_length,
+                    slot_number,
+                    block_time_us,
+                    block_hash,
+                    results,
+                })
+            }
+            QUERY_TYPE_SOLANA_PDA => {
+                let request_commitment = query.string()?;
+                let request_min_context_slot = query.u64()?;
+                let request_data_slice_offset = query.u64()?;
+                let request_data_slice_length = query.u64()?;
+                let pdas = (0..query.u8()?)
+                    .map(|_| {
+                        let program_address = query.address()?;
+                        let seeds = (0..query.u8()?)
+                            .map(|_| Ok(query.var_bytes()?.to_vec().into()))
+                            .collect::<StdResult<Vec<Binary>>>()?;
+                        Ok((program_address, seeds))
+                    })
+                    .collect::<StdResult<Vec<_>>>()?;
+
+                let slot_number = response.u64()?;
+                let block_time_us = response.u64()?;
+                let block_hash = response.bytes32()?.to_vec().into();
+                if response.u8()? as usize != pdas.len() {
+                    Err(ContractError::InvalidQueryResponse {})?
+                }
+                let results = pdas
+                    .into_iter()
+                    .map(|(program_address, seeds)| {
+                        Ok(SolanaPdaData {
+                            program_address,
+                            seeds,
+                            account: response.address()?,
+                            bump: response.u8()?,
+                            lamports: response.u64()?,
+                            rent_epoch: response.u64()?,
+                            executable: response.bool()?,
+                            owner: response.address()?,
+                            data: response.var_bytes()?.to_vec().into(),
+                        })
+                    })
+                    .collect::<StdResult<Vec<_>>>()?;
+
+                QueryResult::SolanaPda(SolanaPdaResult {
+                    request_commitment,
+                    request_min_context_slot,
+                    request_data_slice_offset,
+                    request_data_slice_length,
+                    slot_number,
+                    block_time_us,
+                    block_hash,
+                    results,
+                })
+            }
+            _ => Err(ContractError::UnsupportedQueryType {})?,
+        };
+
+        query.finish()?;
+        response.finish()?;
+
+        Ok(result)
+    }
+}
+
+/// Bounds checked big endian reader, running past the end is an `InvalidQueryResponse`
+/// rather than a panic
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> StdResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or(ContractError::InvalidQueryResponse {})?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> StdResult<[u8; N]> {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(self.bytes(N)?);
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> StdResult<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> StdResult<u16> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    fn u32(&mut self) -> StdResult<u32> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    fn u64(&mut self) -> StdResult<u64> {
+        self.array().map(u64::from_be_bytes)
+    }
+
+    fn bool(&mut self) -> StdResult<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ContractError::InvalidQueryResponse {}.into()),
+        }
+    }
+
+    fn bytes32(&mut self) -> StdResult<&'a [u8]> {
+        self.bytes(32)
+    }
+
+    fn address(&mut self) -> StdResult<ExternalAddress> {
+        self.array().map(ExternalAddress::from)
+    }
+
+    /// uint32 length prefixed bytes
+    fn var_bytes(&mut self) -> StdResult<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.bytes(len)
+    }
+
+    fn string(&mut self) -> StdResult<String> {
+        String::from_utf8(self.var_bytes()?.to_vec())
+            .map_err(|_| ContractError::InvalidQueryResponse {}.into())
+    }
+
+    /// Batch of (20 byte contract address, call data) from an EVM query
+    fn eth_calls(&mut self) -> StdResult<Vec<(Binary, Binary)>> {
+        (0..self.u8()?)
+            .map(|_| {
+                let contract_address = self.bytes(20)?.to_vec().into();
+                let call_data = self.var_bytes()?.to_vec().into();
+                Ok((contract_address, call_data))
+            })
+            .collect()
+    }
+
+    /// Results of an EVM query, one per call of the request batch
+    fn eth_call_results(&mut self, calls: Vec<(Binary, Binary)>) -> StdResult<Vec<EthCallData>> {
+        if self.u8()? as usize != calls.len() {
+            Err(ContractError::InvalidQueryResponse {})?
+        }
+
+        calls
+            .into_iter()
+            .map(|(contract_address, call_data)| {
+                Ok(EthCallData {
+                    contract_address,
+                    call_data,
+                    result: self.var_bytes()?.to_vec().into(),
+                })
+            })
+            .collect()
+    }
+
+    /// Fails on trailing bytes
+    fn finish(self) -> StdResult<()> {
+        if self.pos != self.data.len() {
+            Err(ContractError::InvalidQueryResponse {})?
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{query_response, PerChainQuery};
+
+    #[test]
+    fn deserialize_eth_call_and_solana_account() {
+        let data = query_response(
+            7,
+            &[
+                PerChainQuery::eth_call(2, "0x4d2", &[([0x11; 20], b"\x01\x02", b"\xff")]),
+                PerChainQuery::solana_account(&[([0x22; 32], b"account data")]),
+            ],
+        );
+        let response = QueryResponse::deserialize(&data).unwrap();
+
+        assert_eq!(response.version, 1);
+        assert_eq!(response.request_chain_id, 0);
+        assert_eq!(response.request_id, Binary::from([0xaa; 65].to_vec()));
+        assert_eq!(response.nonce, 7);
+        assert_eq!(response.responses.len(), 2);
+
+        assert_eq!(response.responses[0].chain_id, 2);
+        assert_eq!(
+            response.responses[0].result,
+            QueryResult::EthCall(EthCallResult {
+                request_block_id: "0x4d2".to_string(),
+                block_number: 1234,
+                block_hash: [0xbb; 32].to_vec().into(),
+                block_time_us: 1_700_000_000_000_000,
+                results: vec![EthCallData {
+                    contract_address: [0x11; 20].to_vec().into(),
+                    call_data: b"\x01\x02".to_vec().into(),
+                    result: b"\xff".to_vec().into(),
+                }],
+            })
+        );
+
+        let QueryResult::SolanaAccount(solana) = &response.responses[1].result else {
+            panic!("expected a solana account result");
+        };
+        assert_eq!(solana.request_commitment, "finalized");
+        assert_eq!(solana.slot_number, 5678);
+        assert_eq!(solana.results.len(), 1);
+        assert_eq!(solana.results[0].account, ExternalAddress::from([0x22; 32]));
+        assert_eq!(solana.results[0].owner, ExternalAddress::from([0xdd; 32]));
+        assert!(!solana.results[0].executable);
+        assert_eq!(
+            solana.results[0].data,
+            Binary::from(b"account data".to_vec())
+        );
+    }
+
+    #[test]
+    fn deserialize_eth_call_by_timestamp_and_with_finality() {
+        let calls: [([u8; 20], &[u8], &[u8]); 2] = [
+            ([0x11; 20], b"\x01", b"\xf1"),
+            ([0x12; 20], b"\x02", b"\xf2"),
+        ];
+        let results = calls
+            .iter()
+            .map(|(contract_address, call_data, result)| EthCallData {
+                contract_address: contract_address.to_vec().into(),
+                call_data: call_data.to_vec().into(),
+                result: result.to_vec().into(),
+            })
+            .collect::<Vec<_>>();
+        let target_time_us = 1_700_000_000_000_000;
+        let data = query_response(
+            3,
+            &[
+                PerChainQuery::eth_call_by_timestamp(2, target_time_us, &calls),
+                PerChainQuery::eth_call_with_finality(5, "0x4d2", "safe", &calls),
+            ],
+        );
+        let response = QueryResponse::deserialize(&data).unwrap();
+
+        assert_eq!(response.responses.len(), 2);
+        assert_eq!(response.responses[0].chain_id, 2);
+        assert_eq!(
+            response.responses[0].result,
+            QueryResult::EthCallByTimestamp(EthCallByTimestampResult {
+                request_target_time_us: target_time_us,
+                request_target_block_hint: String::new(),
+                request_following_block_hint: String::new(),
+                target_block_number: 1234,
+                target_block_hash: [0xbb; 32].to_vec().into(),
+                target_block_time_us: target_time_us,
+                following_block_number: 1235,
+                following_block_hash: [0xbc; 32].to_vec().into(),
+                following_block_time_us: target_time_us + 1_000_000,
+                results: results.clone(),
+            })
+        );
+        assert_eq!(response.responses[1].chain_id, 5);
+        assert_eq!(
+            response.responses[1].result,
+            QueryResult::EthCallWithFinality(EthCallWithFinalityResult {
+                request_block_id: "0x4d2".to_string(),
+                request_finality: "safe".to_string(),
+                block_number: 1234,
+                block_hash: [0xbb; 32].to_vec().into(),
+                block_time_us: 1_700_000_000_000_000,
+                results,
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_solana_pda() {
+        let seeds: [&[u8]; 2] = [b"vault", b"\x01"];
+        let data = query_response(
+            4,
+            &[PerChainQuery::solana_pda(&[(
+                [0x33; 32],
+                &seeds,
+                b"pda data",
+            )])],
+        );
+        let response = QueryResponse::deserialize(&data).unwrap();
+
+        assert_eq!(
+            response.responses[0].result,
+            QueryResult::SolanaPda(SolanaPdaResult {
+                request_commitment: "finalized".to_string(),
+                request_min_context_slot: 0,
+                request_data_slice_offset: 0,
+                request_data_slice_length: 0,
+                slot_number: 5678,
+                block_time_us: 1_700_000_000_000_000,
+                block_hash: [0xcc; 32].to_vec().into(),
+                results: vec![SolanaPdaData {
+                    program_address: ExternalAddress::from([0x33; 32]),
+                    seeds: vec![b"vault".to_vec().into(), b"\x01".to_vec().into()],
+                    account: ExternalAddress::from([0xee; 32]),
+                    bump: 254,
+                    lamports: 1_000_000,
+                    rent_epoch: u64::MAX,
+                    executable: true,
+                    owner: ExternalAddress::from([0xdd; 32]),
+                    data: b"pda data".to_vec().into(),
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn mismatched_result_counts_rejected() {
+        let one: [([u8; 20], &[u8], &[u8]); 1] = [([0x11; 20], b"", b"\x01")];
+        let two: [([u8; 20], &[u8], &[u8]); 2] = [one[0], ([0x12; 20], b"", b"\x02")];
+        let seeds: [&[u8]; 1] = [b"seed"];
+        let pda = ([0x33; 32], seeds.as_slice(), b"".as_slice());
+
+        // Each pair requests two items but only answers one
+        let cases = [
+            (
+                PerChainQuery::eth_call_by_timestamp(2, 1, &two),
+                PerChainQuery::eth_call_by_timestamp(2, 1, &one),
+            ),
+            (
+                PerChainQuery::eth_call_with_finality(2, "latest", "finalized", &two),
+                PerChainQuery::eth_call_with_finality(2, "latest", "finalized", &one),
+            ),
+            (
+                PerChainQuery::solana_account(&[([0x22; 32], b""), ([0x23; 32], b"")]),
+                PerChainQuery::solana_account(&[([0x22; 32], b"")]),
+            ),
+            (
+                PerChainQuery::solana_pda(&[pda, pda]),
+                PerChainQuery::solana_pda(&[pda]),
+            ),
+        ];
+        for (mut requested, answered) in cases {
+            let valid = query_response(1, std::slice::from_ref(&requested));
+            QueryResponse::deserialize(&valid).unwrap();
+            requested.response = answered.response;
+            let err = QueryResponse::deserialize(&query_response(1, &[requested])).unwrap_err();
+            assert!(err.to_string().contains("InvalidQueryResponse"));
+        }
+    }
+
+    #[test]
+    fn malformed_responses_rejected() {
+        let eth_call = || PerChainQuery::eth_call(2, "latest", &[([0x11; 20], b"", b"\x01")]);
+        let data = query_response(1, &[eth_call()]);
+        QueryResponse::deserialize(&data).unwrap();
+
+        let err = QueryResponse::deserialize(&data[..data.len() - 1]).unwrap_err();
+        assert!(err.to_string().contains("InvalidQueryResponse"));
+
+        let trailing = [data.as_slice(), &[0]].concat();
+        let err = QueryResponse::deserialize(&trailing).unwrap_err();
+        assert!(err.to_string().contains("InvalidQueryResponse"));
+
+        // Response for another chain than requested
+        let mut mismatched = eth_call();
+        mismatched.response.clear();
+        let mut other_chain = query_response(1, &[mismatched]);
+        let response_chain_pos = other_chain.len() - 7;
+        other_chain[response_chain_pos + 1] = 3;
+        let err = QueryResponse::deserialize(&other_chain).unwrap_err();
+        assert!(err.to_string().contains("InvalidQueryResponse"));
+
+        // Fewer results than calls in the request
+        let mut missing_result = eth_call();
+        missing_result.response = missing_result.response[..48].to_vec();
+        missing_result.response.push(0);
+        let err = QueryResponse::deserialize(&query_response(1, &[missing_result])).unwrap_err();
+        assert!(err.to_string().contains("InvalidQueryResponse"));
+
+        let mut unsupported = eth_call();
+        unsupported.query_type = 9;
+        let err = QueryResponse::deserialize(&query_response(1, &[unsupported])).unwrap_err();
+        assert!(err.to_string().contains("UnsupportedQueryType"));
+    }
+}
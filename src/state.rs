@@ -1,12 +1,89 @@
+use std::fmt;
+
 use cosmwasm_schema::cw_serde;
 use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
 
-use cosmwasm_std::{Binary, StdError, StdResult};
+use cosmwasm_std::{Addr, Attribute, Binary, CanonicalAddr, Empty, StdError, StdResult, Uint128};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{byte_utils::ByteUtils, error::ContractError};
 
 use sha3::{Digest, Keccak256};
 
+/// Defines a 32 byte newtype that is hex encoded in JSON
+macro_rules! bytes32_hex {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub [u8; 32]);
+
+        impl $name {
+            pub fn as_slice(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl From<[u8; 32]> for $name {
+            fn from(bytes: [u8; 32]) -> Self {
+                $name(bytes)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = ContractError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                bytes
+                    .try_into()
+                    .map($name)
+                    .map_err(|_| ContractError::InvalidBytes32Length {})
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&hex::encode(self.0))
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&hex::encode(self.0))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(&s)).map_err(de::Error::custom)?;
+                $name::try_from(bytes.as_slice()).map_err(de::Error::custom)
+            }
+        }
+
+        impl JsonSchema for $name {
+            fn schema_name() -> String {
+                stringify!($name).to_string()
+            }
+
+            fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+                String::json_schema(gen)
+            }
+        }
+    };
+}
+
+bytes32_hex!(
+    /// Address of an emitter or contract on any Wormhole chain, left padded to 32 bytes
+    ExternalAddress
+);
+
+bytes32_hex!(
+    /// Double keccak256 of a VAA body, the message guardians sign
+    VaaDigest
+);
+
 /// Information about this contract's general parameters.
 #[cw_serde]
 pub struct ConfigInfo {
@@ -17,13 +94,18 @@ pub struct ConfigInfo {
     /// The typo is an easter egg.
     pub guardian_set_expirity: u64,
 
-    /// Governance chain (typically Solana, i.e. chain id 1)
-    pub gov_chain: u16,
+    /// The wormhole id of the current chain.
+    pub chain_id: u16,
+}
 
-    /// Address of governance contract (typically 0x0000000000000000000000000000000000000000000000000000000000000004)
+/// `ConfigInfo` of version 0.1.0, which stored the governance emitter. Accepted governance
+/// emitters are now tracked in `GOVERNANCE_EMITTERS`.
+#[cw_serde]
+pub struct LegacyConfigInfo {
+    pub guardian_set_index: u32,
+    pub guardian_set_expirity: u64,
+    pub gov_chain: u16,
     pub gov_address: Vec<u8>,
-
-    /// The wormhole id of the current chain.
     pub chain_id: u16,
 }
 
@@ -38,12 +120,13 @@ pub struct ParsedVAA {
     pub len_signers: u8,
 
     pub emitter_chain: u16,
-    pub emitter_address: Vec<u8>,
+    pub emitter_address: ExternalAddress,
     pub sequence: u64,
     pub consistency_level: u8,
     pub payload: Vec<u8>,
 
-    pub hash: Vec<u8>,
+    pub hash: VaaDigest,
+    pub signer_indexes: Vec<u8>,
 }
 
 impl ParsedVAA {
@@ -89,56 +172,156 @@ impl ParsedVAA {
     pub const SIG_RECOVERY_POS: usize = Self::SIG_DATA_POS + Self::SIG_DATA_LEN;
 
     pub fn deserialize(data: &[u8]) -> StdResult<Self> {
-        let version = data.get_u8(0);
+        VaaRef::parse(data).map(ParsedVAA::from)
+    }
 
-        // Load 4 bytes starting from index 1
-        let guardian_set_index: u32 = data.get_u32(Self::GUARDIAN_SET_INDEX_POS);
-        let len_signers = data.get_u8(Self::LEN_SIGNER_POS) as usize;
-        let body_offset: usize = Self::HEADER_LEN + Self::SIGNATURE_LEN * len_signers;
+    /// Wormhole message id `emitter_chain/emitter_address/sequence`
+    pub fn message_id(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            self.emitter_chain, self.emitter_address, self.sequence
+        )
+    }
+}
 
-        // Hash the body
-        if body_offset >= data.len() {
-            return Err(StdError::generic_err("Invalid VAA".to_string()));
+impl From<VaaRef<'_>> for ParsedVAA {
+    fn from(vaa: VaaRef<'_>) -> Self {
+        ParsedVAA {
+            version: vaa.version(),
+            guardian_set_index: vaa.guardian_set_index(),
+            timestamp: vaa.timestamp(),
+            nonce: vaa.nonce(),
+            len_signers: vaa.len_signers(),
+            emitter_chain: vaa.emitter_chain(),
+            emitter_address: ExternalAddress(
+                vaa.body()
+                    .get_const_bytes(ParsedVAA::VAA_EMITTER_ADDRESS_POS),
+            ),
+            sequence: vaa.sequence(),
+            consistency_level: vaa.consistency_level(),
+            payload: vaa.payload().to_vec(),
+            hash: vaa.digest(),
+            signer_indexes: vaa.signatures().map(|sig| sig.index()).collect(),
         }
+    }
+}
 
-        let body = &data[body_offset..];
-        let mut hasher = Keccak256::new();
-        hasher.update(body);
-        let hash = hasher.finalize().to_vec();
+/// Borrowed view of a raw VAA. The layout is validated once by `parse`, accessors then read
+/// straight from the underlying buffer without copying.
+#[derive(Clone, Copy, Debug)]
+pub struct VaaRef<'a> {
+    data: &'a [u8],
+    body_offset: usize,
+}
 
-        // Rehash the hash
-        let mut hasher = Keccak256::new();
-        hasher.update(hash);
-        let hash = hasher.finalize().to_vec();
+impl<'a> VaaRef<'a> {
+    pub fn parse(data: &'a [u8]) -> StdResult<Self> {
+        if data.len() < ParsedVAA::HEADER_LEN {
+            return Err(StdError::generic_err("Invalid VAA".to_string()));
+        }
 
-        // Signatures valid, apply VAA
-        if body_offset + Self::VAA_PAYLOAD_POS > data.len() {
+        let len_signers = data.get_u8(ParsedVAA::LEN_SIGNER_POS) as usize;
+        let body_offset = ParsedVAA::HEADER_LEN + ParsedVAA::SIGNATURE_LEN * len_signers;
+        if body_offset + ParsedVAA::VAA_PAYLOAD_POS > data.len() {
             return Err(StdError::generic_err("Invalid VAA".to_string()));
         }
 
-        let timestamp = data.get_u32(body_offset);
-        let nonce = data.get_u32(body_offset + Self::VAA_NONCE_POS);
-        let emitter_chain = data.get_u16(body_offset + Self::VAA_EMITTER_CHAIN_POS);
-        let emitter_address = data
-            .get_bytes32(body_offset + Self::VAA_EMITTER_ADDRESS_POS)
-            .to_vec();
-        let sequence = data.get_u64(body_offset + Self::VAA_SEQUENCE_POS);
-        let consistency_level = data.get_u8(body_offset + Self::VAA_CONSISTENCY_LEVEL_POS);
-        let payload = data[body_offset + Self::VAA_PAYLOAD_POS..].to_vec();
-
-        Ok(ParsedVAA {
-            version,
-            guardian_set_index,
-            timestamp,
-            nonce,
-            len_signers: len_signers as u8,
-            emitter_chain,
-            emitter_address,
-            sequence,
-            consistency_level,
-            payload,
-            hash,
-        })
+        Ok(VaaRef { data, body_offset })
+    }
+
+    pub fn version(&self) -> u8 {
+        self.data.get_u8(0)
+    }
+
+    pub fn guardian_set_index(&self) -> u32 {
+        self.data.get_u32(ParsedVAA::GUARDIAN_SET_INDEX_POS)
+    }
+
+    pub fn len_signers(&self) -> u8 {
+        self.data.get_u8(ParsedVAA::LEN_SIGNER_POS)
+    }
+
+    pub fn signatures(&self) -> impl ExactSizeIterator<Item = VaaSignature<'a>> + Clone {
+        self.data[ParsedVAA::HEADER_LEN..self.body_offset]
+            .chunks_exact(ParsedVAA::SIGNATURE_LEN)
+            .map(|bytes| VaaSignature {
+                index: bytes[0],
+                bytes: &bytes[ParsedVAA::SIG_DATA_POS..],
+            })
+    }
+
+    /// The signed part of the VAA
+    pub fn body(&self) -> &'a [u8] {
+        &self.data[self.body_offset..]
+    }
+
+    pub fn timestamp(&self) -> u32 {
+        self.body().get_u32(0)
+    }
+
+    pub fn nonce(&self) -> u32 {
+        self.body().get_u32(ParsedVAA::VAA_NONCE_POS)
+    }
+
+    pub fn emitter_chain(&self) -> u16 {
+        self.body().get_u16(ParsedVAA::VAA_EMITTER_CHAIN_POS)
+    }
+
+    pub fn emitter_address(&self) -> &'a [u8] {
+        let body: &'a [u8] = self.body();
+        &body[ParsedVAA::VAA_EMITTER_ADDRESS_POS..ParsedVAA::VAA_SEQUENCE_POS]
+    }
+
+    pub fn sequence(&self) -> u64 {
+        self.body().get_u64(ParsedVAA::VAA_SEQUENCE_POS)
+    }
+
+    pub fn consistency_level(&self) -> u8 {
+        self.body().get_u8(ParsedVAA::VAA_CONSISTENCY_LEVEL_POS)
+    }
+
+    pub fn payload(&self) -> &'a [u8] {
+        &self.body()[ParsedVAA::VAA_PAYLOAD_POS..]
+    }
+
+    pub fn digest(&self) -> VaaDigest {
+        VaaDigest(Keccak256::digest(Keccak256::digest(self.body())).into())
+    }
+}
+
+/// A single guardian signature, either borrowed from a VAA or from any other guardian-signed
+/// artifact
+#[derive(Clone, Copy, Debug)]
+pub struct VaaSignature<'a> {
+    index: u8,
+    // (r, s, v) signature
+    bytes: &'a [u8],
+}
+
+impl<'a> VaaSignature<'a> {
+    /// Signature length, including the recovery id
+    pub const LEN: usize = ParsedVAA::SIG_DATA_LEN + 1;
+
+    pub fn new(index: u8, bytes: &'a [u8]) -> StdResult<Self> {
+        if bytes.len() != Self::LEN {
+            return Err(StdError::generic_err("Invalid signature length"));
+        }
+
+        Ok(VaaSignature { index, bytes })
+    }
+
+    /// Index of the signer in the guardian set
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Compact (r, s) signature
+    pub fn signature(&self) -> &'a [u8] {
+        &self.bytes[..ParsedVAA::SIG_DATA_LEN]
+    }
+
+    pub fn recovery_id(&self) -> u8 {
+        self.bytes.get_u8(ParsedVAA::SIG_DATA_LEN)
     }
 }
 
@@ -161,6 +344,21 @@ impl GuardianAddress {
     }
 }
 
+/// Guardian signature over an arbitrary digest, `signature` holds the 65 bytes (r, s, v)
+#[cw_serde]
+pub struct GuardianSignature {
+    pub index: u8,
+    pub signature: Binary,
+}
+
+/// Guardian whose signature was verified, identified by its index in the guardian set
+#[cw_serde]
+#[derive(Eq)]
+pub struct GuardianSigner {
+    pub index: u8,
+    pub address: GuardianAddress,
+}
+
 // Guardian set information
 #[cw_serde]
 #[derive(Eq)]
@@ -171,13 +369,262 @@ pub struct GuardianSetInfo {
 }
 
 impl GuardianSetInfo {
-    pub fn quorum(&self) -> usize {
+    pub fn quorum(&self, config: &QuorumConfig) -> usize {
         // allow quorum of 0 for testing purposes...
-        if self.addresses.is_empty() {
+        if cfg!(feature = "testing") && config.test_mode && self.addresses.is_empty() {
             return 0;
         }
-        ((self.addresses.len() * 10 / 3) * 2) / 10 + 1
+        config.rule.quorum(self.addresses.len())
     }
+
+    /// Checks that the guardian set can reach a quorum under the configured rule
+    pub fn validate(&self, config: &QuorumConfig) -> Result<(), ContractError> {
+        if self.addresses.is_empty() {
+            return match cfg!(feature = "testing") && config.test_mode {
+                true => Ok(()),
+                false => Err(ContractError::EmptyGuardianSet {}),
+            };
+        }
+        if self.quorum(config) > self.addresses.len() {
+            return Err(ContractError::InvalidQuorumRule {});
+        }
+        Ok(())
+    }
+}
+
+/// Rule deciding how many guardian signatures a VAA needs
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum QuorumRule {
+    /// Wormhole consensus, more than two thirds of the guardian set
+    Wormhole {},
+    /// Fixed number of signatures regardless of the guardian set size
+    Threshold { signatures: u32 },
+    /// More than `numerator / denominator` of the guardian set
+    Fraction { numerator: u32, denominator: u32 },
+}
+
+impl QuorumRule {
+    pub fn quorum(&self, num_guardians: usize) -> usize {
+        match *self {
+            QuorumRule::Wormhole {} => ((num_guardians * 10 / 3) * 2) / 10 + 1,
+            QuorumRule::Threshold { signatures } => signatures as usize,
+            QuorumRule::Fraction {
+                numerator,
+                denominator,
+            } => {
+                // usize is 32 bits on wasm32, the product only fits in 64 bits
+                let quorum = num_guardians as u64 * numerator as u64 / denominator as u64;
+                quorum as usize + 1
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let valid = match *self {
+            QuorumRule::Wormhole {} => true,
+            QuorumRule::Threshold { signatures } => signatures > 0,
+            QuorumRule::Fraction {
+                numerator,
+                denominator,
+            } => numerator > 0 && numerator < denominator,
+        };
+        match valid {
+            true => Ok(()),
+            false => Err(ContractError::InvalidQuorumRule {}),
+        }
+    }
+}
+
+/// Quorum settings fixed at instantiation
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub struct QuorumConfig {
+    pub rule: QuorumRule,
+    /// Allows empty guardian sets, which accept VAAs without any signature. Only honoured by
+    /// builds with the `testing` feature.
+    pub test_mode: bool,
+}
+
+impl Default for QuorumConfig {
+    fn default() -> Self {
+        QuorumConfig {
+            rule: QuorumRule::Wormhole {},
+            test_mode: false,
+        }
+    }
+}
+
+/// Finality a message was observed with, derived from the VAA consistency level.
+/// Variants are ordered from weakest to strongest guarantee.
+#[cw_serde]
+#[derive(Copy, Eq, PartialOrd, Ord)]
+pub enum Finality {
+    Instant,
+    Safe,
+    Finalized,
+}
+
+impl Finality {
+    pub const SOLANA_CHAIN_ID: u16 = 1;
+
+    pub fn from_consistency_level(emitter_chain: u16, consistency_level: u8) -> Self {
+        // EVM chains publish instantly with 200 and at the safe head with 201, Solana uses 0 for
+        // the confirmed commitment. Any other level is only observed once finalized.
+        match (emitter_chain, consistency_level) {
+            (_, 200) => Finality::Instant,
+            (_, 201) => Finality::Safe,
+            (Self::SOLANA_CHAIN_ID, 0) => Finality::Safe,
+            _ => Finality::Finalized,
+        }
+    }
+
+    pub fn from_u8(value: u8) -> Result<Self, ContractError> {
+        match value {
+            0 => Ok(Finality::Instant),
+            1 => Ok(Finality::Safe),
+            2 => Ok(Finality::Finalized),
+            _ => Err(ContractError::InvalidVAA {}),
+        }
+    }
+}
+
+/// Bounds on a VAA body timestamp relative to the block time, in seconds
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub struct FreshnessPolicy {
+    pub max_age: Option<u64>,
+    pub max_future_drift: Option<u64>,
+}
+
+/// Contract that VAAs of a registered emitter are delivered to
+#[cw_serde]
+pub struct EmitterRegistration {
+    pub receiver: Addr,
+    // Only deliver VAAs in consecutive sequence order
+    pub strict_ordering: bool,
+    /// Payloads are token bridge transfers whose value counts against the governor limit
+    #[serde(default)]
+    pub token_bridge: bool,
+}
+
+/// Optional local admin and its pending transfer, in the layout of cw-ownable's `Ownership`
+#[cw_serde]
+#[derive(Default)]
+pub struct Ownership {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    /// The pending transfer can no longer be accepted once expired
+    pub pending_expiry: Option<Expiration>,
+}
+
+/// Governor limits of an emitter chain over a rolling window of `GOVERNOR_WINDOW` seconds.
+/// VAAs over a limit are queued and released after `release_delay` seconds.
+#[cw_serde]
+pub struct GovernorLimit {
+    pub max_vaas: Option<u64>,
+    /// Maximum USD value of the transfers of token bridge emitters, valued at the prices of
+    /// `TOKEN_PRICES`. Transfers of tokens without a price are not counted.
+    pub max_notional: Option<Uint128>,
+    pub release_delay: u64,
+}
+
+/// VAAs and USD value of transfers delivered from an emitter chain within one block time second
+#[cw_serde]
+#[derive(Default)]
+pub struct GovernorUsage {
+    pub vaas: u64,
+    pub notional: Uint128,
+}
+
+/// Verified VAA held back by the governor until `release_time`
+#[cw_serde]
+pub struct PendingVAA {
+    pub vaa: ParsedVAA,
+    pub receiver: Addr,
+    /// USD value of the transfer, zero unless the emitter is a token bridge
+    pub notional: Uint128,
+    pub queued_at: u64,
+    pub release_time: u64,
+}
+
+/// Verified governance action waiting out the governance delay
+#[cw_serde]
+pub struct PendingGovernanceAction {
+    pub id: u64,
+    /// Governance emitter of the VAA, its actions are dropped when it is removed
+    pub emitter: GovernanceEmitter,
+    pub digest: VaaDigest,
+    pub action: u8,
+    pub payload: Binary,
+    pub queued_at: u64,
+    pub executable_at: u64,
+}
+
+/// Where an executed governance action came from
+#[cw_serde]
+#[derive(Copy)]
+pub enum GovernanceOrigin {
+    /// Governance VAA signed by the guardians
+    Vaa,
+    /// Recovery by the chain governance module
+    Sudo,
+}
+
+/// Executed governance action. `name` and `params` are reported by the handler of the action.
+#[cw_serde]
+pub struct GovernanceLogEntry {
+    pub id: u64,
+    pub action: u8,
+    pub name: String,
+    pub module: String,
+    pub origin: GovernanceOrigin,
+    /// Digest of the governance VAA, None for sudo recoveries
+    pub digest: Option<VaaDigest>,
+    pub params: Vec<Attribute>,
+    pub block_height: u64,
+    pub block_time: u64,
+}
+
+/// Emitter whose VAAs are executed as governance
+#[cw_serde]
+pub struct GovernanceEmitter {
+    pub emitter_chain: u16,
+    pub emitter_address: ExternalAddress,
+}
+
+/// Consumption record of a VAA, indexed by its message id
+#[cw_serde]
+pub struct ConsumedVAA {
+    pub digest: VaaDigest,
+    pub block_height: u64,
+    pub block_time: u64,
+}
+
+/// What the contract did with a consumed VAA
+#[cw_serde]
+pub enum VAAAction {
+    /// Applied a governance action of the Core module
+    Governance { action: u8 },
+    /// Delivered the VAA to the receiver registered for its emitter
+    Dispatched { receiver: Addr },
+    /// Queued by the governor, delivered to the receiver once released
+    Queued { receiver: Addr },
+    /// Consumed before receipts were recorded
+    Legacy {},
+}
+
+/// Receipt of a consumed VAA, keyed by its digest. Legacy receipts carry the block of the
+/// migration that created them and no submitter or emitter information.
+#[cw_serde]
+pub struct VAAReceipt {
+    pub block_height: u64,
+    pub block_time: u64,
+    pub submitter: Option<Addr>,
+    pub emitter_chain: u16,
+    pub emitter_address: ExternalAddress,
+    pub sequence: u64,
+    pub action: VAAAction,
 }
 
 // Wormhole contract generic information
@@ -222,6 +669,88 @@ pub struct GuardianSetUpgrade {
     pub new_guardian_set: GuardianSetInfo,
 }
 
+// action 128
+pub struct SetConsistencyLevel {
+    pub emitter_chain: u16,
+    pub min_finality: Finality,
+}
+
+// action 129
+pub struct SetFreshnessPolicy {
+    pub emitter_chain: u16,
+    pub policy: FreshnessPolicy,
+}
+
+// action 130
+pub struct RegisterEmitter {
+    pub emitter_chain: u16,
+    pub emitter_address: ExternalAddress,
+    pub receiver: Option<CanonicalAddr>,
+    pub strict_ordering: bool,
+    pub token_bridge: bool,
+}
+
+// action 131
+pub struct SetGuardianSetExpiry {
+    pub guardian_set_expirity: u64,
+}
+
+// action 132
+pub struct SetGuardianSetExpiration {
+    pub guardian_set_index: u32,
+    pub expiration_time: u64,
+}
+
+// action 133
+pub struct SetPaused {
+    pub paused: bool,
+}
+
+// action 134, empty payload
+pub struct RevokeAdmin;
+
+// action 135
+pub struct SetGovernorLimit {
+    pub emitter_chain: u16,
+    pub limit: GovernorLimit,
+}
+
+// action 136
+pub struct ReleasePendingVAA {
+    pub digest: VaaDigest,
+}
+
+// action 137
+pub struct SetGovernanceDelay {
+    pub delay: u64,
+}
+
+// actions 138 (add) and 139 (remove)
+pub struct UpdateGovernanceEmitter {
+    pub emitter: GovernanceEmitter,
+}
+
+// action 140
+pub struct CancelPendingGovernance {
+    pub id: u64,
+}
+
+// action 141
+pub struct SetTokenPrice {
+    pub token_chain: u16,
+    pub token_address: ExternalAddress,
+    /// None removes the price
+    pub price: Option<Uint128>,
+}
+
+/// Token bridge transfer, only decoded for the governor limits of token bridge emitters
+pub struct TokenBridgeTransfer {
+    /// Amount normalized to at most 8 decimals, saturated to u128
+    pub amount: Uint128,
+    pub token_chain: u16,
+    pub token_address: ExternalAddress,
+}
+
 impl ContractUpgrade {
     pub fn deserialize(data: &[u8]) -> StdResult<Self> {
         let new_contract = data.get_u64(24);
@@ -262,6 +791,325 @@ impl GuardianSetUpgrade {
     }
 }
 
+impl SetConsistencyLevel {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint16 emitter_chain
+        2   uint8 min_finality (0 = instant, 1 = safe, 2 = finalized)
+        */
+        if data.len() != 3 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        let emitter_chain = data.get_u16(0);
+        let min_finality = Finality::from_u8(data.get_u8(2))?;
+
+        Ok(SetConsistencyLevel {
+            emitter_chain,
+            min_finality,
+        })
+    }
+}
+
+impl SetFreshnessPolicy {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint16 emitter_chain (0 = global policy)
+        2   uint64 max_age (0 = unlimited)
+        10  uint64 max_future_drift (0 = unlimited)
+        */
+        if data.len() != 18 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        let emitter_chain = data.get_u16(0);
+        let max_age = Some(data.get_u64(2)).filter(|v| *v != 0);
+        let max_future_drift = Some(data.get_u64(10)).filter(|v| *v != 0);
+
+        Ok(SetFreshnessPolicy {
+            emitter_chain,
+            policy: FreshnessPolicy {
+                max_age,
+                max_future_drift,
+            },
+        })
+    }
+}
+
+impl RegisterEmitter {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint16 emitter_chain
+        2   [32]uint8 emitter_address
+        34  [32]uint8 receiver (all zeroes to deregister)
+        66  uint8 strict_ordering
+        67  uint8 token_bridge
+        */
+        if data.len() != 68 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        let emitter_chain = data.get_u16(0);
+        let emitter_address = ExternalAddress(data.get_const_bytes(2));
+        let receiver = if data.get_bytes32(34).iter().all(|b| *b == 0) {
+            None
+        } else {
+            Some(data.get_address(34))
+        };
+        let strict_ordering = data.get_u8(66) != 0;
+        let token_bridge = data.get_u8(67) != 0;
+
+        Ok(RegisterEmitter {
+            emitter_chain,
+            emitter_address,
+            receiver,
+            strict_ordering,
+            token_bridge,
+        })
+    }
+}
+
+impl SetGuardianSetExpiry {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint64 guardian_set_expirity (seconds)
+        */
+        if data.len() != 8 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        Ok(SetGuardianSetExpiry {
+            guardian_set_expirity: data.get_u64(0),
+        })
+    }
+}
+
+impl SetGuardianSetExpiration {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint32 guardian_set_index
+        4   uint64 expiration_time (0 = expire immediately)
+        */
+        if data.len() != 12 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        Ok(SetGuardianSetExpiration {
+            guardian_set_index: data.get_u32(0),
+            expiration_time: data.get_u64(4),
+        })
+    }
+}
+
+impl SetPaused {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint8 paused (0 = unpause, 1 = pause)
+        */
+        if data.len() != 1 || data.get_u8(0) > 1 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        Ok(SetPaused {
+            paused: data.get_u8(0) == 1,
+        })
+    }
+}
+
+impl RevokeAdmin {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        if !data.is_empty() {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        Ok(RevokeAdmin)
+    }
+}
+
+impl SetGovernorLimit {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint16 emitter_chain
+        2   uint64 max_vaas (0 = unlimited)
+        10  uint128 max_notional (USD, 0 = unlimited)
+        26  uint64 release_delay (seconds)
+        */
+        if data.len() != 34 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        let emitter_chain = data.get_u16(0);
+        let max_vaas = Some(data.get_u64(2)).filter(|v| *v != 0);
+        let max_notional = Some(Uint128::new(data.get_u128_be(10))).filter(|v| !v.is_zero());
+        let release_delay = data.get_u64(26);
+
+        Ok(SetGovernorLimit {
+            emitter_chain,
+            limit: GovernorLimit {
+                max_vaas,
+                max_notional,
+                release_delay,
+            },
+        })
+    }
+}
+
+impl ReleasePendingVAA {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   [32]uint8 digest
+        */
+        Ok(ReleasePendingVAA {
+            digest: VaaDigest::try_from(data).map_err(|_| ContractError::InvalidVAA {})?,
+        })
+    }
+}
+
+impl SetGovernanceDelay {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint64 delay (seconds, 0 = none)
+        */
+        if data.len() != 8 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        Ok(SetGovernanceDelay {
+            delay: data.get_u64(0),
+        })
+    }
+}
+
+impl UpdateGovernanceEmitter {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint16 emitter_chain
+        2   [32]uint8 emitter_address
+        */
+        if data.len() != 34 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        Ok(UpdateGovernanceEmitter {
+            emitter: GovernanceEmitter {
+                emitter_chain: data.get_u16(0),
+                emitter_address: ExternalAddress(data.get_const_bytes(2)),
+            },
+        })
+    }
+}
+
+impl CancelPendingGovernance {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint64 id of the pending governance action
+        */
+        if data.len() != 8 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        Ok(CancelPendingGovernance {
+            id: data.get_u64(0),
+        })
+    }
+}
+
+impl SetTokenPrice {
+    pub fn deserialize(data: &[u8]) -> Result<Self, ContractError> {
+        /* Payload format
+        0   uint16 token_chain
+        2   [32]uint8 token_address
+        34  uint128 price (USD of one whole token with 8 decimals, 0 = none)
+        */
+        if data.len() != 50 {
+            return Err(ContractError::InvalidVAA {});
+        }
+
+        Ok(SetTokenPrice {
+            token_chain: data.get_u16(0),
+            token_address: ExternalAddress(data.get_const_bytes(2)),
+            price: Some(Uint128::new(data.get_u128_be(34))).filter(|v| !v.is_zero()),
+        })
+    }
+}
+
+impl TokenBridgeTransfer {
+    /// Returns None for payloads that are not a token bridge transfer
+    pub fn deserialize(payload: &[u8]) -> Option<Self> {
+        /* Payload format
+        0   uint8 payload_id (1 = transfer, 3 = transfer with payload)
+        1   uint256 amount
+        33  [32]uint8 token_address
+        65  uint16 token_chain
+        67  [32]uint8 recipient
+        99  uint16 recipient_chain
+        101 uint256 fee or [32]uint8 sender
+        133 []uint8 additional payload of transfers with payload
+        */
+        const MIN_LEN: usize = 133;
+        if payload.len() < MIN_LEN || !matches!(payload.get_u8(0), 1 | 3) {
+            return None;
+        }
+
+        let amount = match payload.get_u256(1) {
+            (0, low) => Uint128::new(low),
+            _ => Uint128::MAX,
+        };
+
+        Some(TokenBridgeTransfer {
+            amount,
+            token_chain: payload.get_u16(65),
+            token_address: ExternalAddress(payload.get_const_bytes(33)),
+        })
+    }
+}
+
 pub const CONFIG: Item<ConfigInfo> = Item::new("config");
+/// Config format of version 0.1.0, only used to migrate the stored config
+pub const LEGACY_CONFIG: Item<LegacyConfigInfo> = Item::new("config");
 pub const GUARDIAN_SET: Map<u32, GuardianSetInfo> = Map::new("guardian_set");
-pub const CONSUMED_VAA_ARCHIVE: Map<&[u8], bool> = Map::new("consumed_vaa_archive");
+/// Absent for contracts instantiated before the quorum became configurable
+pub const QUORUM_CONFIG: Item<QuorumConfig> = Item::new("quorum_config");
+pub const CONSUMED_VAA_ARCHIVE: Map<&[u8], VAAReceipt> = Map::new("consumed_vaa_archive");
+/// Archive format of version 0.1.0, only used to migrate entries to receipts
+pub const LEGACY_CONSUMED_VAA_ARCHIVE: Map<&[u8], bool> = Map::new("consumed_vaa_archive");
+/// Consumed VAAs keyed by message id (emitter_chain, emitter_address, sequence)
+pub const CONSUMED_VAA_BY_MESSAGE_ID: Map<(u16, &[u8], u64), ConsumedVAA> =
+    Map::new("consumed_vaa_by_message_id");
+pub const MIN_CONSISTENCY_LEVEL: Map<u16, Finality> = Map::new("min_consistency_level");
+pub const FRESHNESS_POLICY: Item<FreshnessPolicy> = Item::new("freshness_policy");
+pub const CHAIN_FRESHNESS_POLICY: Map<u16, FreshnessPolicy> = Map::new("chain_freshness_policy");
+pub const EMITTER_REGISTRATIONS: Map<(u16, &[u8]), EmitterRegistration> =
+    Map::new("emitter_registrations");
+/// Highest sequence consumed per (emitter_chain, emitter_address)
+pub const LAST_SEQUENCE: Map<(u16, &[u8]), u64> = Map::new("last_sequence");
+/// Bitmap of consumed sequences, keyed by (emitter_chain, emitter_address, sequence / 64)
+pub const SEEN_SEQUENCES: Map<(u16, &[u8], u64), u64> = Map::new("seen_sequences");
+/// Circuit breaker, while set only governance VAAs are processed. Absent means unpaused.
+pub const PAUSED: Item<bool> = Item::new("paused");
+/// Optional local admin, absent if there never was one
+pub const OWNERSHIP: Item<Ownership> = Item::new("ownership");
+/// Restriction set by the admin on who may submit non-governance VAAs, absent if unrestricted
+pub const ALLOWED_SUBMITTERS: Item<Vec<Addr>> = Item::new("allowed_submitters");
+/// Length in seconds of the rolling window of the governor limits
+pub const GOVERNOR_WINDOW: u64 = 86400;
+pub const GOVERNOR_LIMITS: Map<u16, GovernorLimit> = Map::new("governor_limits");
+/// USD price of one whole token with 8 decimals, keyed by (token_chain, token_address)
+pub const TOKEN_PRICES: Map<(u16, &[u8]), Uint128> = Map::new("token_prices");
+/// Delivered usage per (emitter_chain, block time), entries older than the window are pruned
+pub const GOVERNOR_USAGE: Map<(u16, u64), GovernorUsage> = Map::new("governor_usage");
+/// VAAs queued by the governor, keyed by digest
+pub const PENDING_VAAS: Map<&[u8], PendingVAA> = Map::new("pending_vaas");
+/// Message ids of the queued VAAs of strict ordering emitters, which are released in sequence
+/// order
+pub const PENDING_STRICT_SEQUENCES: Map<(u16, &[u8], u64), Empty> =
+    Map::new("pending_strict_sequences");
+/// Delay in seconds of timelocked governance actions, absent if they apply immediately
+pub const GOVERNANCE_DELAY: Item<u64> = Item::new("governance_delay");
+pub const PENDING_GOVERNANCE: Map<u64, PendingGovernanceAction> = Map::new("pending_governance");
+pub const NEXT_PENDING_GOVERNANCE_ID: Item<u64> = Item::new("next_pending_governance_id");
+/// Append-only log of executed governance actions, keyed by execution order
+pub const GOVERNANCE_HISTORY: Map<u64, GovernanceLogEntry> = Map::new("governance_history");
+pub const GOVERNANCE_HISTORY_LEN: Item<u64> = Item::new("governance_history_len");
+/// Set of accepted governance emitters, keyed by (emitter_chain, emitter_address)
+pub const GOVERNANCE_EMITTERS: Map<(u16, &[u8]), Empty> = Map::new("governance_emitters");
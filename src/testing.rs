@@ -0,0 +1,338 @@
+//! Deterministic test guardians and helpers to build signed VAAs
+
+use k256::ecdsa::SigningKey;
+use sha3::{Digest, Keccak256};
+
+use crate::byte_utils::extend_string_to_32;
+use crate::msg::InstantiateMsg;
+use crate::state::{GuardianAddress, GuardianSetInfo};
+
+pub const GOV_CHAIN: u16 = 1;
+pub const GOV_ADDRESS: [u8; 32] = {
+    let mut address = [0u8; 32];
+    address[31] = 4;
+    address
+};
+pub const CHAIN_ID: u16 = 18;
+
+pub fn guardian_key(i: u8) -> SigningKey {
+    SigningKey::from_bytes(&[i + 1; 32].into()).unwrap()
+}
+
+pub fn guardian_address(key: &SigningKey) -> GuardianAddress {
+    let point = key.verifying_key().to_encoded_point(false);
+    let hash = Keccak256::digest(&point.as_bytes()[1..]);
+    GuardianAddress {
+        bytes: hash[12..].to_vec().into(),
+    }
+}
+
+pub struct Body {
+    pub timestamp: u32,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+    pub consistency_level: u8,
+    pub payload: Vec<u8>,
+}
+
+impl Body {
+    pub fn governance(action: u8, payload: &[u8]) -> Self {
+        let mut module = [0u8; 32];
+        module[28..].copy_from_slice(b"Core");
+        let mut data = module.to_vec();
+        data.push(action);
+        data.extend_from_slice(&CHAIN_ID.to_be_bytes());
+        data.extend_from_slice(payload);
+        Body {
+            timestamp: 0,
+            emitter_chain: GOV_CHAIN,
+            emitter_address: GOV_ADDRESS,
+            sequence: 0,
+            consistency_level: 32,
+            payload: data,
+        }
+    }
+
+    pub fn message(emitter_chain: u16, consistency_level: u8) -> Self {
+        Body {
+            timestamp: 0,
+            emitter_chain,
+            emitter_address: extend_string_to_32("emitter").try_into().unwrap(),
+            sequence: 0,
+            consistency_level,
+            payload: b"hello".to_vec(),
+        }
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut body = self.timestamp.to_be_bytes().to_vec();
+        body.extend_from_slice(&0u32.to_be_bytes());
+        body.extend_from_slice(&self.emitter_chain.to_be_bytes());
+        body.extend_from_slice(&self.emitter_address);
+        body.extend_from_slice(&self.sequence.to_be_bytes());
+        body.push(self.consistency_level);
+        body.extend_from_slice(&self.payload);
+        body
+    }
+}
+
+/// 65 bytes (r, s, v) signature of `digest` by guardian `index`
+pub fn sign_digest(index: u8, digest: &[u8]) -> Vec<u8> {
+    let (signature, recovery_id) = guardian_key(index)
+        .sign_prehash_recoverable(digest)
+        .unwrap();
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(recovery_id.to_byte());
+    bytes
+}
+
+pub fn sign_vaa(guardian_set_index: u32, signers: &[u8], body: &Body) -> Vec<u8> {
+    let body = body.serialize();
+    let digest = Keccak256::digest(Keccak256::digest(&body));
+
+    let mut vaa = vec![1u8];
+    vaa.extend_from_slice(&guardian_set_index.to_be_bytes());
+    vaa.push(signers.len() as u8);
+    for &index in signers {
+        vaa.push(index);
+        vaa.extend_from_slice(&sign_digest(index, &digest));
+    }
+    vaa.extend_from_slice(&body);
+    vaa
+}
+
+pub fn instantiate_msg(num_guardians: u8) -> InstantiateMsg {
+    InstantiateMsg {
+        gov_chain: GOV_CHAIN,
+        gov_address: GOV_ADDRESS.into(),
+        initial_guardian_set: GuardianSetInfo {
+            addresses: (0..num_guardians)
+                .map(|i| guardian_address(&guardian_key(i)))
+                .collect(),
+            expiration_time: 0,
+        },
+        guardian_set_expirity: 86400,
+        chain_id: CHAIN_ID,
+        consistency_levels: vec![],
+        freshness_policy: None,
+        chain_freshness_policies: vec![],
+        quorum_rule: None,
+        test_mode: false,
+        admin: None,
+        governance_delay: 0,
+    }
+}
+
+pub fn guardian_set_upgrade(index: u32, num_guardians: u8) -> Vec<u8> {
+    let mut payload = index.to_be_bytes().to_vec();
+    payload.push(num_guardians);
+    for i in 0..num_guardians {
+        payload.extend_from_slice(&guardian_address(&guardian_key(i)).bytes);
+    }
+    payload
+}
+
+fn push_var_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+/// Per-chain query of a Wormhole Queries request paired with its response
+pub struct PerChainQuery {
+    pub chain_id: u16,
+    pub query_type: u8,
+    pub query: Vec<u8>,
+    pub response: Vec<u8>,
+}
+
+/// Batch of (contract address, call data, result) calls of an EVM query
+type EthCalls<'a> = [([u8; 20], &'a [u8], &'a [u8])];
+
+/// (program address, seeds, data) of a Solana PDA query
+type SolanaPda<'a> = ([u8; 32], &'a [&'a [u8]], &'a [u8]);
+
+fn push_eth_calls(query: &mut Vec<u8>, calls: &EthCalls) {
+    query.push(calls.len() as u8);
+    for (contract_address, call_data, _) in calls {
+        query.extend_from_slice(contract_address);
+        push_var_bytes(query, call_data);
+    }
+}
+
+fn push_eth_results(response: &mut Vec<u8>, calls: &EthCalls) {
+    response.push(calls.len() as u8);
+    for (_, _, result) in calls {
+        push_var_bytes(response, result);
+    }
+}
+
+fn push_solana_request(query: &mut Vec<u8>) {
+    push_var_bytes(query, b"finalized");
+    query.extend_from_slice(&0u64.to_be_bytes());
+    query.extend_from_slice(&0u64.to_be_bytes());
+    query.extend_from_slice(&0u64.to_be_bytes());
+}
+
+fn push_solana_block(response: &mut Vec<u8>) {
+    response.extend_from_slice(&5678u64.to_be_bytes());
+    response.extend_from_slice(&1_700_000_000_000_000u64.to_be_bytes());
+    response.extend_from_slice(&[0xcc; 32]);
+}
+
+impl PerChainQuery {
+    /// `eth_call` at `block_id` of `calls`, each a (contract address, call data, result)
+    pub fn eth_call(chain_id: u16, block_id: &str, calls: &EthCalls) -> Self {
+        let mut query = vec![];
+        push_var_bytes(&mut query, block_id.as_bytes());
+        push_eth_calls(&mut query, calls);
+
+        let mut response = 1234u64.to_be_bytes().to_vec();
+        response.extend_from_slice(&[0xbb; 32]);
+        response.extend_from_slice(&1_700_000_000_000_000u64.to_be_bytes());
+        push_eth_results(&mut response, calls);
+
+        PerChainQuery {
+            chain_id,
+            query_type: 1,
+            query,
+            response,
+        }
+    }
+
+    /// `eth_call` of `calls` at the block of `target_time_us`, answered with block 1234 and its
+    /// successor
+    pub fn eth_call_by_timestamp(chain_id: u16, target_time_us: u64, calls: &EthCalls) -> Self {
+        let mut query = target_time_us.to_be_bytes().to_vec();
+        push_var_bytes(&mut query, b"");
+        push_var_bytes(&mut query, b"");
+        push_eth_calls(&mut query, calls);
+
+        let mut response = 1234u64.to_be_bytes().to_vec();
+        response.extend_from_slice(&[0xbb; 32]);
+        response.extend_from_slice(&target_time_us.to_be_bytes());
+        response.extend_from_slice(&1235u64.to_be_bytes());
+        response.extend_from_slice(&[0xbc; 32]);
+        response.extend_from_slice(&(target_time_us + 1_000_000).to_be_bytes());
+        push_eth_results(&mut response, calls);
+
+        PerChainQuery {
+            chain_id,
+            query_type: 2,
+            query,
+            response,
+        }
+    }
+
+    /// `eth_call` of `calls` at `block_id` once it reached `finality`
+    pub fn eth_call_with_finality(
+        chain_id: u16,
+        block_id: &str,
+        finality: &str,
+        calls: &EthCalls,
+    ) -> Self {
+        let mut query = vec![];
+        push_var_bytes(&mut query, block_id.as_bytes());
+        push_var_bytes(&mut query, finality.as_bytes());
+        push_eth_calls(&mut query, calls);
+
+        let mut response = 1234u64.to_be_bytes().to_vec();
+        response.extend_from_slice(&[0xbb; 32]);
+        response.extend_from_slice(&1_700_000_000_000_000u64.to_be_bytes());
+        push_eth_results(&mut response, calls);
+
+        PerChainQuery {
+            chain_id,
+            query_type: 3,
+            query,
+            response,
+        }
+    }
+
+    /// Solana account query of `accounts`, each a (account, data)
+    pub fn solana_account(accounts: &[([u8; 32], &[u8])]) -> Self {
+        let mut query = vec![];
+        push_solana_request(&mut query);
+        query.push(accounts.len() as u8);
+        for (account, _) in accounts {
+            query.extend_from_slice(account);
+        }
+
+        let mut response = vec![];
+        push_solana_block(&mut response);
+        response.push(accounts.len() as u8);
+        for (_, data) in accounts {
+            response.extend_from_slice(&1_000_000u64.to_be_bytes());
+            response.extend_from_slice(&u64::MAX.to_be_bytes());
+            response.push(0);
+            response.extend_from_slice(&[0xdd; 32]);
+            push_var_bytes(&mut response, data);
+        }
+
+        PerChainQuery {
+            chain_id: 1,
+            query_type: 4,
+            query,
+            response,
+        }
+    }
+
+    /// Solana PDA query of `pdas`, each a (program address, seeds, data). Every PDA resolves to
+    /// account `0xee..` with bump 254.
+    pub fn solana_pda(pdas: &[SolanaPda]) -> Self {
+        let mut query = vec![];
+        push_solana_request(&mut query);
+        query.push(pdas.len() as u8);
+        for (program_address, seeds, _) in pdas {
+            query.extend_from_slice(program_address);
+            query.push(seeds.len() as u8);
+            for seed in seeds.iter() {
+                push_var_bytes(&mut query, seed);
+            }
+        }
+
+        let mut response = vec![];
+        push_solana_block(&mut response);
+        response.push(pdas.len() as u8);
+        for (_, _, data) in pdas {
+            response.extend_from_slice(&[0xee; 32]);
+            response.push(254);
+            response.extend_from_slice(&1_000_000u64.to_be_bytes());
+            response.extend_from_slice(&u64::MAX.to_be_bytes());
+            response.push(1);
+            response.extend_from_slice(&[0xdd; 32]);
+            push_var_bytes(&mut response, data);
+        }
+
+        PerChainQuery {
+            chain_id: 1,
+            query_type: 5,
+            query,
+            response,
+        }
+    }
+}
+
+/// Off-chain Wormhole Queries response wrapping `queries`
+pub fn query_response(nonce: u32, queries: &[PerChainQuery]) -> Vec<u8> {
+    let mut request = vec![1u8];
+    request.extend_from_slice(&nonce.to_be_bytes());
+    request.push(queries.len() as u8);
+    for query in queries {
+        request.extend_from_slice(&query.chain_id.to_be_bytes());
+        request.push(query.query_type);
+        push_var_bytes(&mut request, &query.query);
+    }
+
+    let mut response = vec![1u8];
+    response.extend_from_slice(&0u16.to_be_bytes());
+    response.extend_from_slice(&[0xaa; 65]);
+    push_var_bytes(&mut response, &request);
+    response.push(queries.len() as u8);
+    for query in queries {
+        response.extend_from_slice(&query.chain_id.to_be_bytes());
+        response.push(query.query_type);
+        push_var_bytes(&mut response, &query.response);
+    }
+    response
+}